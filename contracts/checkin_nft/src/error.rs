@@ -9,4 +9,14 @@ pub enum Error {
     NFTNotTransferable = 102,
     TokenNotFound = 103,
     Unauthorized = 104,
+    NoPendingRewards = 105,
+    RewardPayoutFailed = 106,
+    InvalidVestingConfig = 107,
+    NothingToClaim = 108,
+    InvalidRating = 109,
+    InvalidContentHash = 110,
+    ReviewNotFound = 111,
+    PhotoAlreadyAttached = 112,
+    InvalidLevelConfig = 113,
+    VisitTooSoon = 114,
 }
@@ -0,0 +1,117 @@
+use soroban_sdk::{contracttype, Env, String, Vec};
+
+use crate::{CheckinNFT, DataKey, Error, TOKEN_BUMP_AMOUNT, TOKEN_LIFETIME_THRESHOLD};
+
+// Minimum time between two recorded visits to the same place
+pub const MIN_VISIT_INTERVAL_SECONDS: u64 = 86_400;
+
+// Requirement and artwork for one level above the base level.
+// A tier is reached when either of its non-zero thresholds is met.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LevelTier {
+    pub min_visits: u32,
+    pub min_age_seconds: u64,
+    pub image_url: String,
+}
+
+// Level and visit history of a check-in NFT, stored next to it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NftLevel {
+    pub level: u32,                 // Starts at 1, rises with visits and age
+    pub visit_count: u32,
+    pub last_visit_timestamp: u64,
+}
+
+impl LevelTier {
+    fn is_reached(&self, visit_count: u32, age_seconds: u64) -> bool {
+        (self.min_visits > 0 && visit_count >= self.min_visits)
+            || (self.min_age_seconds > 0 && age_seconds >= self.min_age_seconds)
+    }
+}
+
+pub fn validate(tiers: &Vec<LevelTier>) -> Result<(), Error> {
+    for tier in tiers.iter() {
+        if tier.min_visits == 0 && tier.min_age_seconds == 0 {
+            return Err(Error::InvalidLevelConfig);
+        }
+    }
+    Ok(())
+}
+
+pub fn read_tiers(env: &Env, place_id: u32) -> Vec<LevelTier> {
+    env.storage()
+        .instance()
+        .get(&DataKey::PlaceLevels(place_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn write_tiers(env: &Env, place_id: u32, tiers: &Vec<LevelTier>) {
+    let key = DataKey::PlaceLevels(place_id);
+    if tiers.is_empty() {
+        env.storage().instance().remove(&key);
+    } else {
+        env.storage().instance().set(&key, tiers);
+    }
+}
+
+/// Stored level of an NFT; NFTs never visited again are still at their
+/// first visit
+pub fn read_level(env: &Env, nft: &CheckinNFT) -> NftLevel {
+    let key = DataKey::Level(nft.token_id);
+    match env.storage().persistent().get(&key) {
+        Some(level) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+            level
+        }
+        None => NftLevel {
+            level: 1,
+            visit_count: 1,
+            last_visit_timestamp: nft.check_in_timestamp,
+        },
+    }
+}
+
+pub fn write_level(env: &Env, token_id: u64, level: &NftLevel) {
+    let key = DataKey::Level(token_id);
+    env.storage().persistent().set(&key, level);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+}
+
+/// Level the NFT has reached now; level 1 is the base artwork and each
+/// consecutive tier reached adds one level
+pub fn current_level(env: &Env, nft: &CheckinNFT, visit_count: u32) -> u32 {
+    let age_seconds = env.ledger().timestamp().saturating_sub(nft.check_in_timestamp);
+    let mut level = 1;
+
+    for tier in read_tiers(env, nft.place_id).iter() {
+        if !tier.is_reached(visit_count, age_seconds) {
+            break;
+        }
+        level += 1;
+    }
+
+    level
+}
+
+/// Artwork of the NFT at `level`
+pub fn image_url(env: &Env, nft: &CheckinNFT, level: u32) -> String {
+    if level > 1 {
+        if let Some(tier) = read_tiers(env, nft.place_id).get(level - 2) {
+            return tier.image_url;
+        }
+    }
+    nft.image_url.clone()
+}
+
+/// Copy of the NFT showing the artwork of its current level
+pub fn with_current_level(env: &Env, mut nft: CheckinNFT) -> CheckinNFT {
+    let visit_count = read_level(env, &nft).visit_count;
+    nft.image_url = image_url(env, &nft, current_level(env, &nft, visit_count));
+    nft
+}
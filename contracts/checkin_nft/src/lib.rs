@@ -1,8 +1,33 @@
 #![no_std]
 
-use soroban_sdk::{Address, Env, IntoVal, String, Symbol, Vec, contract, contractimpl, contracttype, symbol_short};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec, contract, contractimpl, contracttype};
+use turista_events::{
+    CheckinEvent, CheckinInitializedEvent, ContractEvent, LevelChangedEvent,
+    PendingRewardClaimedEvent, PhotoAttachedEvent, PlaceLevelsUpdatedEvent,
+    ReviewModeratedEvent, ReviewPostedEvent, RewardPaidEvent, RewardPendingEvent,
+    VestingClaimedEvent, VestingGrantedEvent, VisitRecordedEvent,
+};
+use turista_interfaces::TurMinterClient;
+pub use turista_interfaces::VisitProof;
 mod error;
+mod levels;
+mod reviews;
+mod vesting;
 pub use error::Error;
+pub use levels::{LevelTier, NftLevel};
+pub use reviews::{PlaceRating, Review};
+pub use vesting::{VestingConfig, VestingPosition};
+
+// TUR reward paid for each check-in (1000 * 10^7 = 10000000000)
+pub const TUR_REWARD_AMOUNT: i128 = 1000_0000000;
+
+// Ledgers closed in a day, at about 5 seconds per ledger
+const DAY_IN_LEDGERS: u32 = 17280;
+
+// Per-token and per-user entries are bumped to a month whenever they are
+// read or written
+const TOKEN_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const TOKEN_LIFETIME_THRESHOLD: u32 = TOKEN_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
 // NFT data structure
 #[contracttype]
@@ -18,6 +43,14 @@ pub struct CheckinNFT {
     pub image_url: String,
 }
 
+// User-generated photo taken at the place, verifiable against its hash
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PhotoProof {
+    pub content_hash: BytesN<32>,   // SHA-256 of the photo
+    pub uri: Option<String>,        // Where the photo is stored, if public
+}
+
 // Storage keys
 #[contracttype]
 #[derive(Clone)]
@@ -27,6 +60,15 @@ pub enum DataKey {
     NextTokenId,                        // Counter for token IDs
     TurTokenContract,                   // Address of TUR token contract
     Admin,                              // Admin address
+    PendingReward(Address),             // user -> unpaid TUR rewards (persistent)
+    VestingConfig,                      // Global vesting terms for rewards
+    PlaceVestingConfig(u32),            // place_id -> vesting terms override
+    VestingPositions(Address),          // user -> Vec<VestingPosition> (persistent)
+    Review(u64),                        // token_id -> Review (persistent)
+    PlaceRating(u32),                   // place_id -> PlaceRating (persistent)
+    PlaceLevels(u32),                   // place_id -> Vec<LevelTier>
+    Photo(u64),                         // token_id -> PhotoProof (persistent)
+    Level(u64),                         // token_id -> NftLevel (persistent)
 }
 
 
@@ -44,6 +86,13 @@ impl CheckinNFTContract {
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::TurTokenContract, &tur_token_contract);
         env.storage().instance().set(&DataKey::NextTokenId, &1u64);
+
+        // Emit event
+        CheckinInitializedEvent {
+            admin,
+            tur_token_contract,
+        }
+        .publish(&env);
     }
 
     /// Mint a new NFT after check-in
@@ -55,6 +104,7 @@ impl CheckinNFTContract {
         latitude: i64,
         longitude: i64,
         image_url: String,
+        photo: Option<PhotoProof>,
     ) -> Result<u64, Error> {
         // Require authentication
         to.require_auth();
@@ -77,13 +127,14 @@ impl CheckinNFTContract {
             .unwrap_or(1);
 
         // Create NFT
+        let now = env.ledger().timestamp();
         let nft = CheckinNFT {
             token_id,
             place_id,
             place_name: place_name.clone(),
             latitude,
             longitude,
-            check_in_timestamp: env.ledger().timestamp(),
+            check_in_timestamp: now,
             owner: to.clone(),
             image_url: image_url.clone(),
         };
@@ -94,50 +145,385 @@ impl CheckinNFTContract {
         // Mark user as checked in at this place
         env.storage().instance().set(&checkin_key, &token_id);
 
+        if let Some(photo) = &photo {
+            Self::write_photo(&env, token_id, photo);
+        }
+
         // Increment token ID counter
         env.storage().instance().set(&DataKey::NextTokenId, &(token_id + 1));
 
         // Emit event
-        env.events().publish(
-            (symbol_short!("mint"), to.clone(), place_id),
+        CheckinEvent {
             token_id,
-        );
+            owner: to.clone(),
+            place_id,
+            place_name,
+            timestamp: now,
+        }
+        .publish(&env);
+
+        // Rewards for places under a vesting schedule are released over time
+        if let Some(config) = vesting::config_for_place(&env, place_id) {
+            if !config.is_immediate()
+                && vesting::grant(&env, &to, token_id, TUR_REWARD_AMOUNT, &config)
+            {
+                VestingGrantedEvent {
+                    user: to,
+                    token_id,
+                    amount: TUR_REWARD_AMOUNT,
+                    start_timestamp: now,
+                    cliff_seconds: config.cliff_seconds,
+                    duration_seconds: config.duration_seconds,
+                }
+                .publish(&env);
 
-        // Mint 1000 TUR tokens to user as reward (1000 * 10^7 = 10000000000)
-        let tur_amount: i128 = 1000_0000000;
-        let tur_contract: Address = env.storage()
-            .instance()
-            .get(&DataKey::TurTokenContract)
-            .unwrap();
+                return Ok(token_id);
+            }
+        }
 
-        // Invoke TUR token contract to mint tokens to user
-        let contract_address = env.current_contract_address();
-        env.invoke_contract::<()>(
-            &tur_contract,
-            &Symbol::new(&env, "mint_by_authorized"),
-            soroban_sdk::vec![
-                &env,
-                contract_address.into_val(&env),
-                to.clone().into_val(&env),
-                tur_amount.into_val(&env),
-            ],
-        );
+        // Mint TUR reward to user; if the payout fails the check-in is kept
+        // and the reward is recorded as pending so it can be claimed later
+        if Self::pay_reward(&env, &to, TUR_REWARD_AMOUNT) {
+            RewardPaidEvent {
+                user: to,
+                token_id,
+                amount: TUR_REWARD_AMOUNT,
+            }
+            .publish(&env);
+        } else {
+            let total_pending = Self::get_pending_rewards(env.clone(), to.clone()) + TUR_REWARD_AMOUNT;
+            Self::write_pending_rewards(&env, &to, total_pending);
+
+            RewardPendingEvent {
+                user: to,
+                token_id,
+                amount: TUR_REWARD_AMOUNT,
+                total_pending,
+            }
+            .publish(&env);
+        }
+
+        Ok(token_id)
+    }
+
+    /// Retry the payout of rewards that could not be minted at check-in.
+    /// Pays as much as the contract may mint right now and keeps the rest
+    /// pending; returns the amount paid.
+    pub fn claim_pending_rewards(env: Env, user: Address) -> Result<i128, Error> {
+        // Require authentication
+        user.require_auth();
 
-        // Emit reward event
-        env.events().publish(
-            (symbol_short!("tur_rwrd"), to, tur_amount),
+        let pending = Self::get_pending_rewards(env.clone(), user.clone());
+        if pending == 0 {
+            return Err(Error::NoPendingRewards);
+        }
+
+        let amount = pending.min(Self::tur_minter(&env).remaining_minter_allowance(
+            &env.current_contract_address(),
+        ));
+        if amount <= 0 || !Self::pay_reward(&env, &user, amount) {
+            return Err(Error::RewardPayoutFailed);
+        }
+
+        Self::write_pending_rewards(&env, &user, pending - amount);
+
+        // Emit event
+        PendingRewardClaimedEvent {
+            user,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Get TUR rewards owed to a user that have not been paid yet
+    pub fn get_pending_rewards(env: Env, user: Address) -> i128 {
+        let key = DataKey::PendingReward(user);
+        match env.storage().persistent().get(&key) {
+            Some(pending) => {
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+                pending
+            }
+            None => 0,
+        }
+    }
+
+    /// Set the vesting terms applied to every place without an override (only admin)
+    pub fn set_vesting_config(
+        env: Env,
+        config: Option<VestingConfig>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env);
+
+        vesting::write_config(&env, None, config)
+    }
+
+    /// Set the vesting terms for a single place (only admin)
+    pub fn set_place_vesting_config(
+        env: Env,
+        place_id: u32,
+        config: Option<VestingConfig>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env);
+
+        vesting::write_config(&env, Some(place_id), config)
+    }
+
+    /// Get the vesting terms that apply to check-ins at a place
+    pub fn get_vesting_config(env: Env, place_id: u32) -> Option<VestingConfig> {
+        vesting::config_for_place(&env, place_id)
+    }
+
+    /// Get the vesting positions of a user
+    pub fn get_vesting_positions(env: Env, user: Address) -> Vec<VestingPosition> {
+        vesting::read_positions(&env, &user)
+    }
+
+    /// Get the amount of vested TUR a user can claim now
+    pub fn claimable(env: Env, user: Address) -> i128 {
+        vesting::claimable(&env, &user)
+    }
+
+    /// Mint the vested TUR of a user
+    pub fn claim(env: Env, user: Address) -> Result<i128, Error> {
+        // Require authentication
+        user.require_auth();
+
+        let amount = vesting::release(&env, &user);
+        if amount == 0 {
+            return Err(Error::NothingToClaim);
+        }
+
+        if !Self::pay_reward(&env, &user, amount) {
+            return Err(Error::RewardPayoutFailed);
+        }
+
+        // Emit event
+        VestingClaimedEvent { user, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Attach a photo proof to a check-in NFT after minting (only owner, once)
+    pub fn attach_photo(
+        env: Env,
+        owner: Address,
+        token_id: u64,
+        photo: PhotoProof,
+    ) -> Result<(), Error> {
+        // Require authentication
+        owner.require_auth();
+
+        let nft = Self::read_nft(&env, token_id)?;
+        if nft.owner != owner {
+            return Err(Error::Unauthorized);
+        }
+
+        if Self::get_photo(env.clone(), token_id).is_some() {
+            return Err(Error::PhotoAlreadyAttached);
+        }
+
+        Self::write_photo(&env, token_id, &photo);
+
+        // Emit event
+        PhotoAttachedEvent {
             token_id,
-        );
+            owner,
+            content_hash: photo.content_hash,
+            uri: photo.uri,
+        }
+        .publish(&env);
 
-        Ok(token_id)
+        Ok(())
+    }
+
+    /// Get the photo proof attached to a check-in NFT
+    pub fn get_photo(env: Env, token_id: u64) -> Option<PhotoProof> {
+        let key = DataKey::Photo(token_id);
+        let photo = env.storage().persistent().get(&key);
+        if photo.is_some() {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+        }
+        photo
+    }
+
+    /// Post or edit the review attached to a check-in NFT (only its owner)
+    pub fn post_review(
+        env: Env,
+        reviewer: Address,
+        token_id: u64,
+        rating: u32,
+        content_hash: Bytes,
+    ) -> Result<(), Error> {
+        // Require authentication
+        reviewer.require_auth();
+
+        reviews::validate(rating, &content_hash)?;
+
+        // Only visitors holding the check-in NFT can review the place
+        let nft = Self::read_nft(&env, token_id)?;
+        if nft.owner != reviewer {
+            return Err(Error::Unauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        let previous = reviews::read_review(&env, token_id);
+        let review = Review {
+            token_id,
+            place_id: nft.place_id,
+            reviewer: reviewer.clone(),
+            rating,
+            content_hash: content_hash.clone(),
+            created_at: previous.as_ref().map_or(now, |review| review.created_at),
+            updated_at: now,
+            hidden: previous.as_ref().is_some_and(|review| review.hidden),
+        };
+
+        reviews::write_review(&env, previous.as_ref(), &review);
+
+        // Emit event
+        ReviewPostedEvent {
+            token_id,
+            place_id: nft.place_id,
+            reviewer,
+            rating,
+            content_hash,
+            edited: previous.is_some(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Hide or restore an abusive review (only admin)
+    pub fn set_review_hidden(
+        env: Env,
+        token_id: u64,
+        hidden: bool,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env);
+
+        let previous = reviews::read_review(&env, token_id)
+            .ok_or(Error::ReviewNotFound)?;
+        let review = Review {
+            hidden,
+            ..previous.clone()
+        };
+
+        reviews::write_review(&env, Some(&previous), &review);
+
+        // Emit event
+        ReviewModeratedEvent {
+            token_id,
+            place_id: review.place_id,
+            hidden,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the review attached to a check-in NFT
+    pub fn get_review(env: Env, token_id: u64) -> Result<Review, Error> {
+        reviews::read_review(&env, token_id).ok_or(Error::ReviewNotFound)
+    }
+
+    /// Get the rating sum and count of visible reviews for a place
+    pub fn get_place_rating(env: Env, place_id: u32) -> PlaceRating {
+        reviews::read_place_rating(&env, place_id)
+    }
+
+    /// Get the average rating of a place scaled by 100 (0 if no reviews)
+    pub fn get_place_average_rating(env: Env, place_id: u32) -> u32 {
+        reviews::read_place_rating(&env, place_id).average_x100()
     }
 
     /// Get NFT by token ID
     pub fn get_nft(env: Env, token_id: u64) -> Result<CheckinNFT, Error> {
-        env.storage()
-            .instance()
-            .get(&DataKey::NFT(token_id))
-            .ok_or(Error::TokenNotFound)
+        let nft = Self::read_nft(&env, token_id)?;
+        Ok(levels::with_current_level(&env, nft))
+    }
+
+    /// Get the current level and visit history of an NFT
+    pub fn get_level(env: Env, token_id: u64) -> Result<NftLevel, Error> {
+        let nft = Self::read_nft(&env, token_id)?;
+        let mut level = levels::read_level(&env, &nft);
+        level.level = levels::current_level(&env, &nft, level.visit_count);
+        Ok(level)
+    }
+
+    /// Get the artwork URI for the current level of an NFT
+    pub fn token_uri(env: Env, token_id: u64) -> Result<String, Error> {
+        Ok(Self::get_nft(env, token_id)?.image_url)
+    }
+
+    /// Configure the level tiers and their artwork for a place (only admin)
+    pub fn set_place_levels(
+        env: Env,
+        place_id: u32,
+        tiers: Vec<LevelTier>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env);
+
+        levels::validate(&tiers)?;
+        levels::write_tiers(&env, place_id, &tiers);
+
+        // Emit event
+        PlaceLevelsUpdatedEvent {
+            place_id,
+            tier_count: tiers.len(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the level tiers configured for a place
+    pub fn get_place_levels(env: Env, place_id: u32) -> Vec<LevelTier> {
+        levels::read_tiers(&env, place_id)
+    }
+
+    /// Record a repeat visit to a place the user already checked in at
+    pub fn record_visit(env: Env, user: Address, place_id: u32) -> Result<u32, Error> {
+        // Require authentication
+        user.require_auth();
+
+        let token_id = Self::get_checkin_token_id(env.clone(), user, place_id)
+            .ok_or(Error::TokenNotFound)?;
+        let nft = Self::read_nft(&env, token_id)?;
+        let mut level = levels::read_level(&env, &nft);
+
+        let now = env.ledger().timestamp();
+        if now < level.last_visit_timestamp + levels::MIN_VISIT_INTERVAL_SECONDS {
+            return Err(Error::VisitTooSoon);
+        }
+
+        level.visit_count += 1;
+        level.last_visit_timestamp = now;
+
+        // Emit event
+        VisitRecordedEvent {
+            token_id,
+            user: nft.owner.clone(),
+            place_id,
+            visit_count: level.visit_count,
+            timestamp: now,
+        }
+        .publish(&env);
+
+        Ok(Self::update_level(&env, &nft, level))
+    }
+
+    /// Persist the current level of an NFT, e.g. after it aged into a new tier
+    pub fn refresh_level(env: Env, token_id: u64) -> Result<u32, Error> {
+        let nft = Self::read_nft(&env, token_id)?;
+        let level = levels::read_level(&env, &nft);
+        Ok(Self::update_level(&env, &nft, level))
     }
 
     /// Get all NFTs owned by a user
@@ -154,7 +540,7 @@ impl CheckinNFTContract {
         for token_id in 1..next_token_id {
             if let Some(nft) = env.storage().instance().get::<DataKey, CheckinNFT>(&DataKey::NFT(token_id)) {
                 if nft.owner == owner {
-                    nfts.push_back(nft);
+                    nfts.push_back(levels::with_current_level(&env, nft));
                 }
             }
         }
@@ -174,6 +560,37 @@ impl CheckinNFTContract {
         env.storage().instance().get(&checkin_key)
     }
 
+    /// Get the token ID of a user's check-in at a place if they visited it at
+    /// or after `since_timestamp` (meant to be called by partner contracts)
+    pub fn verify_visit(
+        env: Env,
+        user: Address,
+        place_id: u32,
+        since_timestamp: u64,
+    ) -> Option<u64> {
+        Self::find_visit(&env, user, place_id, since_timestamp)
+            .map(|proof| proof.token_id)
+    }
+
+    /// Batch variant of `verify_visit`, returning a proof with the token ID
+    /// and visit time for every place that satisfies the condition
+    pub fn verify_visits(
+        env: Env,
+        user: Address,
+        place_ids: Vec<u32>,
+        since_timestamp: u64,
+    ) -> Vec<VisitProof> {
+        let mut proofs = Vec::new(&env);
+
+        for place_id in place_ids.iter() {
+            if let Some(proof) = Self::find_visit(&env, user.clone(), place_id, since_timestamp) {
+                proofs.push_back(proof);
+            }
+        }
+
+        proofs
+    }
+
     /// Transfer function - ALWAYS FAILS (Soulbound NFTs)
     pub fn transfer(
         _env: Env,
@@ -194,217 +611,142 @@ impl CheckinNFTContract {
         
         next_token_id - 1
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
-    
-    // Import TUR token contract for testing
-    mod tur_token {
-        soroban_sdk::contractimport!(
-            file = "../target/wasm32v1-none/release/tur_token.optimized.wasm"
-        );
-    }
-
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let contract_id = env.register(CheckinNFTContract {}, ());
-        let client = CheckinNFTContractClient::new(&env, &contract_id);
-
-        let admin = Address::generate(&env);
-        let tur_contract = Address::generate(&env);
-
-        client.initialize(&admin, &tur_contract);
+    fn find_visit(
+        env: &Env,
+        user: Address,
+        place_id: u32,
+        since_timestamp: u64,
+    ) -> Option<VisitProof> {
+        let token_id = Self::get_checkin_token_id(env.clone(), user, place_id)?;
+        let nft = Self::read_nft(env, token_id).ok()?;
+        let visited_at = levels::read_level(env, &nft).last_visit_timestamp;
+
+        if visited_at < since_timestamp {
+            return None;
+        }
 
-        assert_eq!(client.total_supply(), 0);
+        Some(VisitProof {
+            place_id,
+            token_id,
+            visited_at,
+        })
     }
 
-    #[test]
-    fn test_mint() {
-        let env = Env::default();
-        env.mock_all_auths_allowing_non_root_auth();
+    fn read_nft(env: &Env, token_id: u64) -> Result<CheckinNFT, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::NFT(token_id))
+            .ok_or(Error::TokenNotFound)
+    }
 
-        // Register and initialize TUR token contract
-        let tur_contract_id = env.register(tur_token::WASM, ());
-        let tur_client = tur_token::Client::new(&env, &tur_contract_id);
-        let admin = Address::generate(&env);
-        tur_client.initialize(
-            &admin,
-            &String::from_str(&env, "Turista Token"),
-            &String::from_str(&env, "TUR"),
-            &7,
-            &10_000_000_0000000,
-        );
+    fn write_photo(env: &Env, token_id: u64, photo: &PhotoProof) {
+        let key = DataKey::Photo(token_id);
+        env.storage().persistent().set(&key, photo);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+    }
 
-        // Register CheckinNFT contract
-        let contract_id = env.register(CheckinNFTContract {}, ());
-        let client = CheckinNFTContractClient::new(&env, &contract_id);
-        let user = Address::generate(&env);
+    /// Store the current level of an NFT, emitting an event when it changed
+    fn update_level(env: &Env, nft: &CheckinNFT, mut level: NftLevel) -> u32 {
+        let previous_level = level.level;
+        level.level = levels::current_level(env, nft, level.visit_count);
+        levels::write_level(env, nft.token_id, &level);
+
+        if level.level != previous_level {
+            LevelChangedEvent {
+                token_id: nft.token_id,
+                owner: nft.owner.clone(),
+                place_id: nft.place_id,
+                previous_level,
+                level: level.level,
+                image_url: levels::image_url(env, nft, level.level),
+            }
+            .publish(env);
+        }
 
-        client.initialize(&admin, &tur_contract_id);
-        
-        // Authorize CheckinNFT contract to mint TUR tokens
-        tur_client.set_authorized_minter(&contract_id, &true);
-
-        let place_id = 1u32;
-        let place_name = String::from_str(&env, "Plaza de Armas");
-        let latitude = -13516754i64; // -13.516754 * 1e6
-        let longitude = -71978516i64; // -71.978516 * 1e6
-        let image_url = String::from_str(&env, "/nft-plaza-armas.png");
-
-        let token_id = client.mint(
-            &user,
-            &place_id,
-            &place_name,
-            &latitude,
-            &longitude,
-            &image_url,
-        );
+        level.level
+    }
 
-        assert_eq!(token_id, 1);
-        assert_eq!(client.total_supply(), 1);
-        assert!(client.has_checked_in(&user, &place_id));
+    fn require_admin(env: &Env) {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap();
 
-        let nft = client.get_nft(&token_id);
-        assert_eq!(nft.owner, user);
-        assert_eq!(nft.place_id, place_id);
-        assert_eq!(nft.place_name, place_name);
+        admin.require_auth();
     }
 
-    #[test]
-    fn test_cannot_checkin_twice() {
-        let env = Env::default();
-        env.mock_all_auths_allowing_non_root_auth();
-
-        // Register and initialize TUR token contract
-        let tur_contract_id = env.register(tur_token::WASM, ());
-        let tur_client = tur_token::Client::new(&env, &tur_contract_id);
-        let admin = Address::generate(&env);
-        tur_client.initialize(
-            &admin,
-            &String::from_str(&env, "Turista Token"),
-            &String::from_str(&env, "TUR"),
-            &7,
-            &10_000_000_0000000,
+    /// Invoke TUR token contract to mint tokens to user, returning false
+    /// instead of reverting when the call fails
+    fn pay_reward(env: &Env, to: &Address, amount: i128) -> bool {
+        let result = Self::tur_minter(env).try_mint_by_authorized(
+            &env.current_contract_address(),
+            to,
+            &amount,
         );
 
-        // Register CheckinNFT contract
-        let contract_id = env.register(CheckinNFTContract {}, ());
-        let client = CheckinNFTContractClient::new(&env, &contract_id);
-        let user = Address::generate(&env);
+        matches!(result, Ok(Ok(())))
+    }
 
-        client.initialize(&admin, &tur_contract_id);
-        
-        // Authorize CheckinNFT contract to mint TUR tokens
-        tur_client.set_authorized_minter(&contract_id, &true);
+    fn write_pending_rewards(env: &Env, user: &Address, pending: i128) {
+        let key = DataKey::PendingReward(user.clone());
+        if pending == 0 {
+            env.storage().persistent().remove(&key);
+            return;
+        }
 
-        let place_id = 1u32;
-        let place_name = String::from_str(&env, "Plaza de Armas");
-        let latitude = -13516754i64;
-        let longitude = -71978516i64;
-        let image_url = String::from_str(&env, "/nft-plaza-armas.png");
+        env.storage().persistent().set(&key, &pending);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+    }
 
-        // First check-in should succeed
-        client.mint(&user, &place_id, &place_name, &latitude, &longitude, &image_url);
+    fn tur_minter(env: &Env) -> TurMinterClient<'_> {
+        let tur_contract: Address = env.storage()
+            .instance()
+            .get(&DataKey::TurTokenContract)
+            .unwrap();
 
-        // Second check-in should fail
-        let result = client.try_mint(&user, &place_id, &place_name, &latitude, &longitude, &image_url);
-        assert_eq!(result, Err(Ok(Error::AlreadyCheckedIn)));
+        TurMinterClient::new(env, &tur_contract)
     }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, Env};
 
     #[test]
-    fn test_transfer_fails() {
+    fn test_initialize() {
         let env = Env::default();
-        env.mock_all_auths_allowing_non_root_auth();
-
-        // Register and initialize TUR token contract
-        let tur_contract_id = env.register(tur_token::WASM, ());
-        let tur_client = tur_token::Client::new(&env, &tur_contract_id);
-        let admin = Address::generate(&env);
-        tur_client.initialize(
-            &admin,
-            &String::from_str(&env, "Turista Token"),
-            &String::from_str(&env, "TUR"),
-            &7,
-            &10_000_000_0000000,
-        );
-
-        // Register CheckinNFT contract
         let contract_id = env.register(CheckinNFTContract {}, ());
         let client = CheckinNFTContractClient::new(&env, &contract_id);
-        let user1 = Address::generate(&env);
-        let user2 = Address::generate(&env);
 
-        client.initialize(&admin, &tur_contract_id);
-        
-        // Authorize CheckinNFT contract to mint TUR tokens
-        tur_client.set_authorized_minter(&contract_id, &true);
-
-        let place_id = 1u32;
-        let token_id = client.mint(
-            &user1,
-            &place_id,
-            &String::from_str(&env, "Plaza de Armas"),
-            &-13516754i64,
-            &-71978516i64,
-            &String::from_str(&env, "/nft-plaza-armas.png"),
-        );
+        let admin = Address::generate(&env);
+        let tur_contract = Address::generate(&env);
+
+        client.initialize(&admin, &tur_contract);
 
-        // Transfer should fail (soulbound)
-        let result = client.try_transfer(&user1, &user2, &token_id);
-        assert_eq!(result, Err(Ok(Error::NFTNotTransferable)));
+        assert_eq!(client.total_supply(), 0);
     }
 
     #[test]
-    fn test_get_user_nfts() {
+    fn test_invalid_vesting_config() {
         let env = Env::default();
-        env.mock_all_auths_allowing_non_root_auth();
+        env.mock_all_auths();
 
-        // Register and initialize TUR token contract
-        let tur_contract_id = env.register(tur_token::WASM, ());
-        let tur_client = tur_token::Client::new(&env, &tur_contract_id);
-        let admin = Address::generate(&env);
-        tur_client.initialize(
-            &admin,
-            &String::from_str(&env, "Turista Token"),
-            &String::from_str(&env, "TUR"),
-            &7,
-            &10_000_000_0000000,
-        );
-
-        // Register CheckinNFT contract
         let contract_id = env.register(CheckinNFTContract {}, ());
         let client = CheckinNFTContractClient::new(&env, &contract_id);
-        let user = Address::generate(&env);
 
-        client.initialize(&admin, &tur_contract_id);
-        
-        // Authorize CheckinNFT contract to mint TUR tokens
-        tur_client.set_authorized_minter(&contract_id, &true);
-
-        // Mint 2 NFTs for the user
-        client.mint(
-            &user,
-            &1u32,
-            &String::from_str(&env, "Plaza de Armas"),
-            &-13516754i64,
-            &-71978516i64,
-            &String::from_str(&env, "/nft-plaza-armas.png"),
-        );
-
-        client.mint(
-            &user,
-            &2u32,
-            &String::from_str(&env, "Qoricancha"),
-            &-13519722i64,
-            &-71975556i64,
-            &String::from_str(&env, "/nft-qoricancha.png"),
-        );
+        client.initialize(&Address::generate(&env), &Address::generate(&env));
 
-        let nfts = client.get_user_nfts(&user);
-        assert_eq!(nfts.len(), 2);
+        // Cliff cannot be longer than the vesting duration
+        let result = client.try_set_vesting_config(&Some(VestingConfig {
+            cliff_seconds: 200,
+            duration_seconds: 100,
+        }));
+        assert_eq!(result, Err(Ok(Error::InvalidVestingConfig)));
     }
 }
@@ -0,0 +1,101 @@
+use soroban_sdk::{contracttype, Address, Bytes, Env, IntoVal, TryFromVal, Val};
+
+use crate::{DataKey, Error, TOKEN_BUMP_AMOUNT, TOKEN_LIFETIME_THRESHOLD};
+
+// Longest accepted content reference (a binary CIDv1 is 36 bytes)
+const MAX_CONTENT_HASH_LEN: u32 = 64;
+
+// Review posted by the owner of a check-in NFT
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Review {
+    pub token_id: u64,
+    pub place_id: u32,
+    pub reviewer: Address,
+    pub rating: u32,            // 1-5
+    pub content_hash: Bytes,    // e.g. IPFS CID of the review text
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub hidden: bool,
+}
+
+// Aggregate of the visible reviews of a place
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlaceRating {
+    pub rating_sum: u64,
+    pub review_count: u32,
+}
+
+impl PlaceRating {
+    /// Average rating scaled by 100 (e.g. 425 = 4.25 stars)
+    pub fn average_x100(&self) -> u32 {
+        if self.review_count == 0 {
+            return 0;
+        }
+        (self.rating_sum * 100 / self.review_count as u64) as u32
+    }
+
+    fn add(&mut self, rating: u32) {
+        self.rating_sum += rating as u64;
+        self.review_count += 1;
+    }
+
+    fn remove(&mut self, rating: u32) {
+        self.rating_sum -= rating as u64;
+        self.review_count -= 1;
+    }
+}
+
+pub fn validate(rating: u32, content_hash: &Bytes) -> Result<(), Error> {
+    if !(1..=5).contains(&rating) {
+        return Err(Error::InvalidRating);
+    }
+    if content_hash.is_empty() || content_hash.len() > MAX_CONTENT_HASH_LEN {
+        return Err(Error::InvalidContentHash);
+    }
+    Ok(())
+}
+
+pub fn read_review(env: &Env, token_id: u64) -> Option<Review> {
+    read(env, &DataKey::Review(token_id))
+}
+
+pub fn read_place_rating(env: &Env, place_id: u32) -> PlaceRating {
+    read(env, &DataKey::PlaceRating(place_id)).unwrap_or_default()
+}
+
+/// Store a review, keeping the place aggregate in sync with the
+/// previous version of the review (if any)
+pub fn write_review(env: &Env, previous: Option<&Review>, review: &Review) {
+    let mut rating = read_place_rating(env, review.place_id);
+
+    if let Some(previous) = previous {
+        if !previous.hidden {
+            rating.remove(previous.rating);
+        }
+    }
+    if !review.hidden {
+        rating.add(review.rating);
+    }
+
+    write(env, &DataKey::PlaceRating(review.place_id), &rating);
+    write(env, &DataKey::Review(review.token_id), review);
+}
+
+fn read<T: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<T> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+    }
+    value
+}
+
+fn write<T: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &T) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+}
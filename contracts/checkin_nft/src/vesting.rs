@@ -0,0 +1,222 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+use turista_events::{ContractEvent, VestingConfigUpdatedEvent};
+
+use crate::{DataKey, Error, TOKEN_BUMP_AMOUNT, TOKEN_LIFETIME_THRESHOLD};
+
+// Rewards a user can have vesting at once; check-ins beyond it are paid
+// right away
+const MAX_VESTING_POSITIONS: u32 = 32;
+
+// Vesting terms applied to check-in rewards.
+// A duration of 0 pays the reward immediately, which lets a place opt out
+// of a global vesting configuration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingConfig {
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+}
+
+// Reward released linearly between start and start + duration,
+// with nothing released before the cliff
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingPosition {
+    pub token_id: u64,
+    pub total_amount: i128,
+    pub claimed_amount: i128,
+    pub start_timestamp: u64,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+}
+
+impl VestingConfig {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.cliff_seconds > self.duration_seconds {
+            return Err(Error::InvalidVestingConfig);
+        }
+        Ok(())
+    }
+
+    pub fn is_immediate(&self) -> bool {
+        self.duration_seconds == 0
+    }
+}
+
+impl VestingPosition {
+    /// Amount released at `now`, claimed or not
+    pub fn vested_amount(&self, now: u64) -> i128 {
+        let elapsed = now.saturating_sub(self.start_timestamp);
+
+        if elapsed < self.cliff_seconds {
+            return 0;
+        }
+        if elapsed >= self.duration_seconds {
+            return self.total_amount;
+        }
+
+        self.total_amount * elapsed as i128 / self.duration_seconds as i128
+    }
+
+    /// Amount released at `now` that has not been claimed yet
+    pub fn claimable_amount(&self, now: u64) -> i128 {
+        self.vested_amount(now) - self.claimed_amount
+    }
+}
+
+/// Get the vesting terms for a place, falling back to the global config
+pub fn config_for_place(env: &Env, place_id: u32) -> Option<VestingConfig> {
+    env.storage()
+        .instance()
+        .get(&DataKey::PlaceVestingConfig(place_id))
+        .or_else(|| env.storage().instance().get(&DataKey::VestingConfig))
+}
+
+/// Set (or remove) the global vesting terms, or those of a single place
+pub fn write_config(
+    env: &Env,
+    place_id: Option<u32>,
+    config: Option<VestingConfig>,
+) -> Result<(), Error> {
+    let key = match place_id {
+        Some(place_id) => DataKey::PlaceVestingConfig(place_id),
+        None => DataKey::VestingConfig,
+    };
+
+    match &config {
+        Some(config) => {
+            config.validate()?;
+            env.storage().instance().set(&key, config);
+        }
+        None => env.storage().instance().remove(&key),
+    }
+
+    VestingConfigUpdatedEvent {
+        place_id,
+        enabled: config.is_some(),
+        cliff_seconds: config.as_ref().map_or(0, |config| config.cliff_seconds),
+        duration_seconds: config.as_ref().map_or(0, |config| config.duration_seconds),
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+pub fn read_positions(env: &Env, user: &Address) -> Vec<VestingPosition> {
+    let key = DataKey::VestingPositions(user.clone());
+    match env.storage().persistent().get(&key) {
+        Some(positions) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+            positions
+        }
+        None => Vec::new(env),
+    }
+}
+
+fn write_positions(env: &Env, user: &Address, positions: &Vec<VestingPosition>) {
+    let key = DataKey::VestingPositions(user.clone());
+    if positions.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+
+    env.storage().persistent().set(&key, positions);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TOKEN_LIFETIME_THRESHOLD, TOKEN_BUMP_AMOUNT);
+}
+
+// Merge the fully vested positions into the first of them, which then
+// holds their unclaimed amounts and is claimable right away
+fn compact(positions: Vec<VestingPosition>, now: u64) -> Vec<VestingPosition> {
+    let mut compacted = Vec::new(positions.env());
+    let mut vested: Option<(u32, VestingPosition)> = None;
+
+    for position in positions.iter() {
+        if position.vested_amount(now) < position.total_amount {
+            compacted.push_back(position);
+            continue;
+        }
+
+        let unclaimed = position.total_amount - position.claimed_amount;
+        match &mut vested {
+            Some((_, merged)) => merged.total_amount += unclaimed,
+            None => {
+                vested = Some((
+                    compacted.len(),
+                    VestingPosition {
+                        token_id: position.token_id,
+                        total_amount: unclaimed,
+                        claimed_amount: 0,
+                        start_timestamp: position.start_timestamp,
+                        cliff_seconds: 0,
+                        duration_seconds: 0,
+                    },
+                ));
+            }
+        }
+    }
+
+    if let Some((index, merged)) = vested {
+        compacted.insert(index, merged);
+    }
+    compacted
+}
+
+/// Record a new vesting position for a check-in reward. Returns false,
+/// recording nothing, when the user already has the maximum number of
+/// rewards vesting.
+pub fn grant(
+    env: &Env,
+    user: &Address,
+    token_id: u64,
+    amount: i128,
+    config: &VestingConfig,
+) -> bool {
+    let mut positions = compact(read_positions(env, user), env.ledger().timestamp());
+    if positions.len() >= MAX_VESTING_POSITIONS {
+        return false;
+    }
+
+    positions.push_back(VestingPosition {
+        token_id,
+        total_amount: amount,
+        claimed_amount: 0,
+        start_timestamp: env.ledger().timestamp(),
+        cliff_seconds: config.cliff_seconds,
+        duration_seconds: config.duration_seconds,
+    });
+    write_positions(env, user, &positions);
+    true
+}
+
+/// Total amount the user can claim right now
+pub fn claimable(env: &Env, user: &Address) -> i128 {
+    let now = env.ledger().timestamp();
+    read_positions(env, user)
+        .iter()
+        .map(|position| position.claimable_amount(now))
+        .sum()
+}
+
+/// Mark everything vested so far as claimed, dropping fully released
+/// positions, and return the amount released
+pub fn release(env: &Env, user: &Address) -> i128 {
+    let now = env.ledger().timestamp();
+    let mut released = 0;
+    let mut remaining = Vec::new(env);
+
+    for mut position in read_positions(env, user).iter() {
+        released += position.claimable_amount(now);
+        position.claimed_amount = position.vested_amount(now);
+
+        if position.claimed_amount < position.total_amount {
+            remaining.push_back(position);
+        }
+    }
+
+    write_positions(env, user, &remaining);
+    released
+}
@@ -0,0 +1,486 @@
+use checkin_nft::{
+    DataKey, Error, LevelTier, NftLevel, PhotoProof, VestingConfig, VisitProof,
+    TUR_REWARD_AMOUNT,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{Events, Ledger},
+    vec, Address, Bytes, BytesN, Env, IntoVal, String, TryFromVal,
+};
+use turista_events::{ContractEvent, RewardPaidEvent};
+use turista_test_support::{Turista, PLAZA_DE_ARMAS, QORICANCHA};
+
+// Partner contract granting discounts to recent visitors
+#[contract]
+struct PartnerHotel;
+
+#[contractimpl]
+impl PartnerHotel {
+    pub fn discount_bps(env: Env, checkin_nft: Address, guest: Address, place_id: u32) -> u32 {
+        let since = env.ledger().timestamp().saturating_sub(30 * 86_400);
+        let client = turista_interfaces::CheckinVerifierClient::new(&env, &checkin_nft);
+        match client.verify_visit(&guest, &place_id, &since) {
+            Some(_) => 1_000,
+            None => 0,
+        }
+    }
+}
+
+// Layout of the NFTs stored before photo proofs and levels were added
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct StoredCheckinNFT {
+    token_id: u64,
+    place_id: u32,
+    place_name: String,
+    latitude: i64,
+    longitude: i64,
+    check_in_timestamp: u64,
+    owner: Address,
+    image_url: String,
+}
+
+#[test]
+fn test_mint() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+    let user = turista.traveler();
+
+    let place_id = PLAZA_DE_ARMAS.id;
+    let token_id = turista.check_in(&user, &PLAZA_DE_ARMAS);
+
+    // Reward payout is published with the typed event schema
+    let (emitter, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(emitter, client.address);
+    assert_eq!(
+        topics,
+        vec![&env, RewardPaidEvent::NAME.into_val(&env), turista_events::SCHEMA_VERSION.into_val(&env)]
+    );
+    assert_eq!(
+        RewardPaidEvent::try_from_val(&env, &data).unwrap(),
+        RewardPaidEvent { user: user.clone(), token_id, amount: TUR_REWARD_AMOUNT }
+    );
+
+    assert_eq!(token_id, 1);
+    assert_eq!(client.total_supply(), 1);
+    assert!(client.has_checked_in(&user, &place_id));
+
+    let nft = client.get_nft(&token_id);
+    assert_eq!(nft.owner, user);
+    assert_eq!(nft.place_id, place_id);
+    assert_eq!(nft.place_name, String::from_str(&env, PLAZA_DE_ARMAS.name));
+}
+
+#[test]
+fn test_cannot_checkin_twice() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let user = turista.traveler();
+
+    // First check-in should succeed
+    turista.check_in(&user, &PLAZA_DE_ARMAS);
+
+    // Second check-in should fail
+    let result = turista.nft.try_mint(
+        &user,
+        &PLAZA_DE_ARMAS.id,
+        &String::from_str(&env, PLAZA_DE_ARMAS.name),
+        &PLAZA_DE_ARMAS.latitude,
+        &PLAZA_DE_ARMAS.longitude,
+        &String::from_str(&env, PLAZA_DE_ARMAS.image_url),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(Error::AlreadyCheckedIn)));
+}
+
+#[test]
+fn test_transfer_fails() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let user1 = turista.traveler();
+    let user2 = turista.traveler();
+
+    let token_id = turista.check_in(&user1, &PLAZA_DE_ARMAS);
+
+    // Transfer should fail (soulbound)
+    let result = turista.nft.try_transfer(&user1, &user2, &token_id);
+    assert_eq!(result, Err(Ok(Error::NFTNotTransferable)));
+}
+
+#[test]
+fn test_get_user_nfts() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let user = turista.traveler();
+
+    // Mint 2 NFTs for the user
+    turista.check_in(&user, &PLAZA_DE_ARMAS);
+    turista.check_in(&user, &QORICANCHA);
+
+    let nfts = turista.nft.get_user_nfts(&user);
+    assert_eq!(nfts.len(), 2);
+}
+
+#[test]
+fn test_pending_reward_when_payout_fails() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+
+    // Revoke the minter rights so reward payouts fail
+    turista
+        .tur
+        .set_authorized_minter(&turista.admin, &client.address, &false);
+    let user = turista.traveler();
+
+    // Check-in is kept even though the reward cannot be minted
+    let token_id = turista.check_in(&user, &PLAZA_DE_ARMAS);
+
+    assert_eq!(token_id, 1);
+    assert!(client.has_checked_in(&user, &PLAZA_DE_ARMAS.id));
+    assert_eq!(turista.tur.balance(&user), 0);
+    assert_eq!(client.get_pending_rewards(&user), TUR_REWARD_AMOUNT);
+    env.as_contract(&client.address, || {
+        let key = DataKey::PendingReward(user.clone());
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+
+    // Claiming still fails while the contract is not a minter
+    let result = client.try_claim_pending_rewards(&user);
+    assert_eq!(result, Err(Ok(Error::RewardPayoutFailed)));
+    assert_eq!(client.get_pending_rewards(&user), TUR_REWARD_AMOUNT);
+
+    // Once authorized, the pending reward is paid out
+    turista
+        .tur
+        .set_authorized_minter(&turista.admin, &client.address, &true);
+    assert_eq!(client.claim_pending_rewards(&user), TUR_REWARD_AMOUNT);
+    assert_eq!(turista.tur.balance(&user), TUR_REWARD_AMOUNT);
+    assert_eq!(client.get_pending_rewards(&user), 0);
+
+    // Nothing left to claim
+    let result = client.try_claim_pending_rewards(&user);
+    assert_eq!(result, Err(Ok(Error::NoPendingRewards)));
+}
+
+#[test]
+fn test_vested_rewards() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+    let user = turista.traveler();
+
+    // Vest rewards globally over 100 seconds with a 10 second cliff,
+    // but pay Qoricancha check-ins immediately
+    client.set_vesting_config(&Some(VestingConfig {
+        cliff_seconds: 10,
+        duration_seconds: 100,
+    }));
+    client.set_place_vesting_config(&QORICANCHA.id, &Some(VestingConfig {
+        cliff_seconds: 0,
+        duration_seconds: 0,
+    }));
+
+    env.ledger().set_timestamp(1_000);
+    turista.check_in(&user, &PLAZA_DE_ARMAS);
+    turista.check_in(&user, &QORICANCHA);
+
+    assert_eq!(turista.tur.balance(&user), TUR_REWARD_AMOUNT);
+    assert_eq!(client.get_vesting_positions(&user).len(), 1);
+
+    // Nothing is released before the cliff
+    env.ledger().set_timestamp(1_005);
+    assert_eq!(client.claimable(&user), 0);
+    assert_eq!(client.try_claim(&user), Err(Ok(Error::NothingToClaim)));
+
+    // Linear release after the cliff
+    env.ledger().set_timestamp(1_025);
+    assert_eq!(client.claimable(&user), TUR_REWARD_AMOUNT / 4);
+    assert_eq!(client.claim(&user), TUR_REWARD_AMOUNT / 4);
+    assert_eq!(client.claimable(&user), 0);
+
+    // Remainder once fully vested; the position is then removed
+    env.ledger().set_timestamp(2_000);
+    assert_eq!(client.claim(&user), TUR_REWARD_AMOUNT * 3 / 4);
+    assert_eq!(turista.tur.balance(&user), TUR_REWARD_AMOUNT * 2);
+    assert_eq!(client.get_vesting_positions(&user).len(), 0);
+}
+
+#[test]
+fn test_vesting_positions_stay_bounded() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+    let user = turista.traveler();
+    let check_in = |place_id: u32| {
+        client.mint(
+            &user,
+            &place_id,
+            &String::from_str(&env, "Cusco"),
+            &PLAZA_DE_ARMAS.latitude,
+            &PLAZA_DE_ARMAS.longitude,
+            &String::from_str(&env, PLAZA_DE_ARMAS.image_url),
+            &None,
+        )
+    };
+
+    client.set_vesting_config(&Some(VestingConfig {
+        cliff_seconds: 0,
+        duration_seconds: 100,
+    }));
+
+    // Fully vested positions are merged when a new one is granted
+    env.ledger().set_timestamp(1_000);
+    check_in(100);
+    check_in(101);
+    env.ledger().set_timestamp(1_200);
+    let token_id = check_in(102);
+    let positions = client.get_vesting_positions(&user);
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions.get(0).unwrap().total_amount, TUR_REWARD_AMOUNT * 2);
+    assert_eq!(positions.get(1).unwrap().token_id, token_id);
+    assert_eq!(client.claimable(&user), TUR_REWARD_AMOUNT * 2);
+
+    // Check-ins beyond the cap are paid right away
+    for place_id in 103..133 {
+        check_in(place_id);
+    }
+    assert_eq!(client.get_vesting_positions(&user).len(), 32);
+    assert_eq!(turista.tur.balance(&user), 0);
+    check_in(133);
+    assert_eq!(client.get_vesting_positions(&user).len(), 32);
+    assert_eq!(turista.tur.balance(&user), TUR_REWARD_AMOUNT);
+
+    env.ledger().set_timestamp(1_300);
+    assert_eq!(client.claimable(&user), TUR_REWARD_AMOUNT * 33);
+    env.as_contract(&client.address, || {
+        let key = DataKey::VestingPositions(user.clone());
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+    });
+}
+
+#[test]
+fn test_reviews() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+    let user1 = turista.traveler();
+    let user2 = turista.traveler();
+
+    let place_id = PLAZA_DE_ARMAS.id;
+    let token_ids = [
+        turista.check_in(&user1, &PLAZA_DE_ARMAS),
+        turista.check_in(&user2, &PLAZA_DE_ARMAS),
+    ];
+    let cid = Bytes::from_slice(&env, b"bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+
+    // Only the NFT owner can review, with a rating between 1 and 5
+    let result = client.try_post_review(&user2, &token_ids[0], &5, &cid);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    let result = client.try_post_review(&user1, &token_ids[0], &6, &cid);
+    assert_eq!(result, Err(Ok(Error::InvalidRating)));
+    let result = client.try_post_review(&user1, &token_ids[0], &5, &Bytes::new(&env));
+    assert_eq!(result, Err(Ok(Error::InvalidContentHash)));
+
+    client.post_review(&user1, &token_ids[0], &5, &cid);
+    client.post_review(&user2, &token_ids[1], &2, &cid);
+    assert_eq!(client.get_place_rating(&place_id).review_count, 2);
+    assert_eq!(client.get_place_average_rating(&place_id), 350);
+
+    // Editing replaces the previous rating
+    client.post_review(&user2, &token_ids[1], &4, &cid);
+    assert_eq!(client.get_place_rating(&place_id).review_count, 2);
+    assert_eq!(client.get_place_average_rating(&place_id), 450);
+    assert_eq!(client.get_review(&token_ids[1]).rating, 4);
+
+    // Hidden reviews are excluded from the aggregate
+    client.set_review_hidden(&token_ids[1], &true);
+    assert!(client.get_review(&token_ids[1]).hidden);
+    assert_eq!(client.get_place_rating(&place_id).review_count, 1);
+    assert_eq!(client.get_place_average_rating(&place_id), 500);
+
+    client.set_review_hidden(&token_ids[1], &false);
+    assert_eq!(client.get_place_average_rating(&place_id), 450);
+
+    let result = client.try_get_review(&99);
+    assert_eq!(result, Err(Ok(Error::ReviewNotFound)));
+
+    // Reviews do not grow the instance entry
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&DataKey::Review(token_ids[0])));
+        assert!(env.storage().persistent().has(&DataKey::PlaceRating(place_id)));
+        assert!(!env.storage().instance().has(&DataKey::Review(token_ids[0])));
+    });
+}
+
+#[test]
+fn test_photo_proof() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+    let user1 = turista.traveler();
+    let user2 = turista.traveler();
+
+    let photo = PhotoProof {
+        content_hash: BytesN::from_array(&env, &[7u8; 32]),
+        uri: Some(String::from_str(&env, "ipfs://bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku")),
+    };
+
+    // Photo proof supplied at mint time
+    let token_id = client.mint(
+        &user1,
+        &PLAZA_DE_ARMAS.id,
+        &String::from_str(&env, PLAZA_DE_ARMAS.name),
+        &PLAZA_DE_ARMAS.latitude,
+        &PLAZA_DE_ARMAS.longitude,
+        &String::from_str(&env, PLAZA_DE_ARMAS.image_url),
+        &Some(photo.clone()),
+    );
+    assert_eq!(client.get_photo(&token_id), Some(photo.clone()));
+
+    // Photo proof attached afterwards, only by the owner and only once
+    let token_id = turista.check_in(&user2, &PLAZA_DE_ARMAS);
+    assert_eq!(client.get_photo(&token_id), None);
+
+    let result = client.try_attach_photo(&user1, &token_id, &photo);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    let photo_without_uri = PhotoProof {
+        uri: None,
+        ..photo.clone()
+    };
+    client.attach_photo(&user2, &token_id, &photo_without_uri);
+    assert_eq!(client.get_photo(&token_id), Some(photo_without_uri));
+
+    let result = client.try_attach_photo(&user2, &token_id, &photo);
+    assert_eq!(result, Err(Ok(Error::PhotoAlreadyAttached)));
+}
+
+#[test]
+fn test_levels() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+    let user = turista.traveler();
+
+    // Level 2 after a second visit, level 3 after 3 visits or a year
+    let place_id = PLAZA_DE_ARMAS.id;
+    let silver = String::from_str(&env, "/nft-plaza-armas-silver.png");
+    let gold = String::from_str(&env, "/nft-plaza-armas-gold.png");
+    client.set_place_levels(&place_id, &vec![
+        &env,
+        LevelTier { min_visits: 2, min_age_seconds: 0, image_url: silver.clone() },
+        LevelTier { min_visits: 3, min_age_seconds: 31_536_000, image_url: gold.clone() },
+    ]);
+
+    let result = client.try_set_place_levels(&place_id, &vec![
+        &env,
+        LevelTier { min_visits: 0, min_age_seconds: 0, image_url: gold.clone() },
+    ]);
+    assert_eq!(result, Err(Ok(Error::InvalidLevelConfig)));
+
+    env.ledger().set_timestamp(1_000);
+    let token_id = turista.check_in(&user, &PLAZA_DE_ARMAS);
+    assert_eq!(client.get_level(&token_id).level, 1);
+    assert_eq!(client.token_uri(&token_id), String::from_str(&env, PLAZA_DE_ARMAS.image_url));
+
+    // Repeat visits are limited to one per day
+    let result = client.try_record_visit(&user, &place_id);
+    assert_eq!(result, Err(Ok(Error::VisitTooSoon)));
+
+    env.ledger().set_timestamp(1_000 + 86_400);
+    assert_eq!(client.record_visit(&user, &place_id), 2);
+    let level = client.get_level(&token_id);
+    assert_eq!(level.level, 2);
+    assert_eq!(level.visit_count, 2);
+    assert_eq!(client.get_nft(&token_id).image_url, silver);
+
+    // Aging into the next tier shows up in views right away and is
+    // persisted (with an event) once the level is refreshed
+    env.ledger().set_timestamp(1_000 + 31_536_000);
+    assert_eq!(client.token_uri(&token_id), gold);
+    assert_eq!(client.get_level(&token_id).level, 3);
+    assert_eq!(client.get_user_nfts(&user).get(0).unwrap().image_url, gold);
+    assert_eq!(client.refresh_level(&token_id), 3);
+
+    // Visiting without a check-in is rejected
+    let result = client.try_record_visit(&user, &QORICANCHA.id);
+    assert_eq!(result, Err(Ok(Error::TokenNotFound)));
+}
+
+#[test]
+fn test_reads_nfts_stored_before_levels() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+    let user = turista.traveler();
+
+    let stored = StoredCheckinNFT {
+        token_id: 1,
+        place_id: PLAZA_DE_ARMAS.id,
+        place_name: String::from_str(&env, PLAZA_DE_ARMAS.name),
+        latitude: PLAZA_DE_ARMAS.latitude,
+        longitude: PLAZA_DE_ARMAS.longitude,
+        check_in_timestamp: 1_000,
+        owner: user.clone(),
+        image_url: String::from_str(&env, PLAZA_DE_ARMAS.image_url),
+    };
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&DataKey::NFT(1), &stored);
+        env.storage().instance().set(&DataKey::UserPlaceCheckin(user.clone(), 1), &1u64);
+        env.storage().instance().set(&DataKey::NextTokenId, &2u64);
+    });
+
+    let nft = client.get_nft(&1);
+    assert_eq!(nft.check_in_timestamp, stored.check_in_timestamp);
+    assert_eq!(nft.image_url, stored.image_url);
+    assert_eq!(client.get_user_nfts(&user).len(), 1);
+    assert_eq!(client.get_photo(&1), None);
+    assert_eq!(
+        client.get_level(&1),
+        NftLevel { level: 1, visit_count: 1, last_visit_timestamp: 1_000 }
+    );
+
+    // Old NFTs can level up
+    let silver = String::from_str(&env, "/nft-plaza-armas-silver.png");
+    client.set_place_levels(&1, &vec![
+        &env,
+        LevelTier { min_visits: 2, min_age_seconds: 0, image_url: silver.clone() },
+    ]);
+    env.ledger().set_timestamp(1_000 + 86_400);
+    assert_eq!(client.record_visit(&user, &1), 2);
+    assert_eq!(client.token_uri(&1), silver);
+}
+
+#[test]
+fn test_verify_visit() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.nft;
+    let user = turista.traveler();
+
+    env.ledger().set_timestamp(1_000);
+    let token_id = turista.check_in(&user, &PLAZA_DE_ARMAS);
+
+    assert_eq!(client.verify_visit(&user, &1u32, &1_000), Some(token_id));
+    assert_eq!(client.verify_visit(&user, &1u32, &1_001), None);
+    assert_eq!(client.verify_visit(&user, &2u32, &0), None);
+
+    // Batch only returns the places that satisfy the condition
+    let proofs = client.verify_visits(&user, &vec![&env, 1u32, 2u32], &500);
+    assert_eq!(proofs, vec![&env, VisitProof { place_id: 1, token_id, visited_at: 1_000 }]);
+
+    // Partner contract checks visits cross-contract
+    let hotel_id = env.register(PartnerHotel {}, ());
+    let hotel = PartnerHotelClient::new(&env, &hotel_id);
+    assert_eq!(hotel.discount_bps(&client.address, &user, &1u32), 1_000);
+
+    // A repeat visit renews the proof once the first one is too old
+    env.ledger().set_timestamp(1_000 + 60 * 86_400);
+    assert_eq!(hotel.discount_bps(&client.address, &user, &1u32), 0);
+    client.record_visit(&user, &1u32);
+    assert_eq!(hotel.discount_bps(&client.address, &user, &1u32), 1_000);
+}
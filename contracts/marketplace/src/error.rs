@@ -9,4 +9,5 @@ pub enum Error {
     TransferFailed = 302,
     Unauthorized = 303,
     Overflow = 304,
+    InvalidFee = 305,
 }
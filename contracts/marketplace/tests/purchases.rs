@@ -0,0 +1,125 @@
+use marketplace::{Error, DEFAULT_PLATFORM_FEE_BPS};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use turista_test_support::Turista;
+
+#[test]
+fn test_purchase_with_xlm() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+
+    let amount_xlm = 50_0000000i128; // 50 XLM
+    let buyer = turista.funded_buyer(amount_xlm * 2); // Mint extra for fees
+    let merchant = turista.merchant();
+
+    turista.marketplace.purchase_with_xlm(&buyer, &merchant, &amount_xlm);
+
+    // Verify balances
+    let platform_fee = amount_xlm / 100;
+    let merchant_amount = amount_xlm - platform_fee;
+    assert_eq!(turista.xlm.balance(&turista.platform), platform_fee);
+    assert_eq!(turista.xlm.balance(&merchant), merchant_amount);
+}
+
+#[test]
+fn test_purchase_with_discount() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+
+    let amount_xlm = 30_0000000i128; // 30 XLM
+    let amount_tur = 5000_0000000i128; // 5000 TUR
+    let buyer = turista.funded_buyer(amount_xlm * 2);
+    let merchant = turista.merchant();
+    turista.tur.mint(&turista.admin, &buyer, &amount_tur);
+
+    // Approve marketplace to burn TUR tokens
+    turista.tur.approve(
+        &buyer,
+        &turista.marketplace.address,
+        &amount_tur,
+        &turista.approval_expiration(),
+    );
+
+    turista
+        .marketplace
+        .purchase_with_discount(&buyer, &merchant, &amount_xlm, &amount_tur);
+
+    // Verify XLM balances
+    let platform_fee = amount_xlm / 100;
+    let merchant_amount = amount_xlm - platform_fee;
+    assert_eq!(turista.xlm.balance(&turista.platform), platform_fee);
+    assert_eq!(turista.xlm.balance(&merchant), merchant_amount);
+
+    // Verify TUR was burned (balance should be 0)
+    assert_eq!(turista.tur.balance(&buyer), 0);
+}
+
+#[test]
+fn test_purchase_requires_registered_merchant() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.marketplace;
+
+    let amount_xlm = 10_0000000i128; // 10 XLM
+    let amount_tur = 100_0000000i128; // 100 TUR
+    let buyer = turista.funded_buyer(amount_xlm * 2);
+    let merchant = turista.merchant();
+    turista.tur.mint(&turista.admin, &buyer, &amount_tur);
+    turista.tur.approve(
+        &buyer,
+        &turista.marketplace.address,
+        &amount_tur,
+        &turista.approval_expiration(),
+    );
+
+    let unknown = Address::generate(&env);
+    assert!(!client.is_merchant(&unknown));
+    assert_eq!(
+        client.try_purchase_with_xlm(&buyer, &unknown, &amount_xlm),
+        Err(Ok(Error::InvalidMerchant))
+    );
+    assert_eq!(
+        client.try_purchase_with_discount(&buyer, &unknown, &amount_xlm, &amount_tur),
+        Err(Ok(Error::InvalidMerchant))
+    );
+
+    // A removed merchant can no longer sell
+    client.set_merchant(&merchant, &false);
+    assert!(!client.is_merchant(&merchant));
+    assert_eq!(
+        client.try_purchase_with_xlm(&buyer, &merchant, &amount_xlm),
+        Err(Ok(Error::InvalidMerchant))
+    );
+
+    // Nothing moved
+    assert_eq!(turista.xlm.balance(&buyer), amount_xlm * 2);
+    assert_eq!(turista.tur.balance(&buyer), amount_tur);
+
+    // Registering it again lets it sell
+    client.set_merchant(&merchant, &true);
+    client.purchase_with_xlm(&buyer, &merchant, &amount_xlm);
+    assert_eq!(turista.xlm.balance(&merchant), 9_9000000);
+}
+
+#[test]
+fn test_platform_fee_bps() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let client = &turista.marketplace;
+
+    let amount_xlm = 40_0000000i128; // 40 XLM
+    let buyer = turista.funded_buyer(amount_xlm);
+    let merchant = turista.merchant();
+
+    assert_eq!(client.get_platform_fee_bps(), DEFAULT_PLATFORM_FEE_BPS);
+    assert_eq!(
+        client.try_set_platform_fee_bps(&10_001),
+        Err(Ok(Error::InvalidFee))
+    );
+
+    // 2.5% fee
+    client.set_platform_fee_bps(&250);
+    client.purchase_with_xlm(&buyer, &merchant, &amount_xlm);
+
+    assert_eq!(turista.xlm.balance(&turista.platform), 1_0000000);
+    assert_eq!(turista.xlm.balance(&merchant), 39_0000000);
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Clone, Debug, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    InvalidAmount = 400,
+    InsufficientStake = 401,
+    NothingToWithdraw = 402,
+    StillCoolingDown = 403,
+    InvalidTierConfig = 404,
+    Overflow = 405,
+    AlreadyInitialized = 406,
+}
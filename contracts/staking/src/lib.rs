@@ -0,0 +1,602 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env};
+use turista_events::{
+    ContractEvent, CooldownUpdatedEvent, StakeWithdrawnEvent, StakedEvent,
+    StakingInitializedEvent, TierConfigUpdatedEvent, UnstakedEvent,
+};
+pub use turista_interfaces::Tier;
+mod error;
+mod tiers;
+pub use error::Error;
+pub use tiers::{TierConfig, TierThreshold};
+
+// Ledgers closed in a day, at about 5 seconds per ledger
+const DAY_IN_LEDGERS: u32 = 17280;
+
+// The instance (admin, token, tiers) is bumped to a week whenever less
+// than six days are left
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+// Stakes are bumped to a month whenever they are read or written
+const STAKE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const STAKE_LIFETIME_THRESHOLD: u32 = STAKE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+// Storage keys
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    TurTokenContract,
+    TierConfig,
+    CooldownSeconds,
+    TotalStaked,
+    Stake(Address),     // persistent, user -> Stake
+    Unbonding(Address), // persistent, user -> Unbonding
+}
+
+// TUR locked by a user. `since` is the average time the staked TUR was
+// locked at, so adding to a stake does not reset its duration but cannot
+// inherit the duration of a small old stake either.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stake {
+    pub amount: i128,
+    pub since: u64,
+}
+
+// Unstaked TUR waiting for the cooldown to end
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unbonding {
+    pub amount: i128,
+    pub release_at: u64,
+}
+
+#[contract]
+pub struct Staking;
+
+#[contractimpl]
+impl Staking {
+    /// Initialize the staking contract with the TUR token, the tier
+    /// thresholds and the unbonding cooldown. Only callable once, by the
+    /// admin.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        tur_token_contract: Address,
+        tiers: TierConfig,
+        cooldown_seconds: u64,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        // Require admin authentication
+        admin.require_auth();
+
+        tiers.validate()?;
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::TurTokenContract, &tur_token_contract);
+        env.storage().instance().set(&DataKey::TierConfig, &tiers);
+        env.storage().instance().set(&DataKey::CooldownSeconds, &cooldown_seconds);
+        extend_instance(&env);
+
+        // Emit event
+        StakingInitializedEvent {
+            admin,
+            tur_token_contract,
+            cooldown_seconds,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Lock TUR of the user in the contract
+    pub fn stake(env: Env, user: Address, amount: i128) -> Result<(), Error> {
+        // Require user authentication
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let now = env.ledger().timestamp();
+        let stake = Self::get_stake(env.clone(), user.clone());
+        let staked = stake.amount.checked_add(amount).ok_or(Error::Overflow)?;
+        let since = stake
+            .amount
+            .checked_mul(stake.since as i128)
+            .and_then(|old| old.checked_add(amount.checked_mul(now as i128)?))
+            .ok_or(Error::Overflow)?
+            / staked;
+
+        Self::tur_client(&env).transfer(&user, &env.current_contract_address(), &amount);
+        Self::write_stake(
+            &env,
+            &user,
+            &Stake {
+                amount: staked,
+                since: since as u64,
+            },
+        );
+        Self::add_total_staked(&env, amount);
+        extend_instance(&env);
+
+        // Emit event
+        StakedEvent {
+            user,
+            amount,
+            staked,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Start unbonding part of a stake; it can be withdrawn once the
+    /// cooldown is over. Unstaking again restarts the cooldown for the
+    /// whole unbonding amount.
+    pub fn unstake(env: Env, user: Address, amount: i128) -> Result<(), Error> {
+        // Require user authentication
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let mut stake = Self::get_stake(env.clone(), user.clone());
+        if stake.amount < amount {
+            return Err(Error::InsufficientStake);
+        }
+
+        stake.amount -= amount;
+        Self::write_stake(&env, &user, &stake);
+        Self::add_total_staked(&env, -amount);
+
+        let release_at = env
+            .ledger()
+            .timestamp()
+            .saturating_add(Self::get_cooldown(env.clone()));
+        let unbonding = Unbonding {
+            amount: Self::get_unbonding(env.clone(), user.clone())
+                .map_or(0, |unbonding| unbonding.amount)
+                + amount,
+            release_at,
+        };
+        let key = DataKey::Unbonding(user.clone());
+        env.storage().persistent().set(&key, &unbonding);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, STAKE_LIFETIME_THRESHOLD, STAKE_BUMP_AMOUNT);
+        extend_instance(&env);
+
+        // Emit event
+        UnstakedEvent {
+            user,
+            amount,
+            release_at,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the unbonded TUR to the user once the cooldown is over
+    pub fn withdraw(env: Env, user: Address) -> Result<i128, Error> {
+        // Require user authentication
+        user.require_auth();
+
+        let unbonding =
+            Self::get_unbonding(env.clone(), user.clone()).ok_or(Error::NothingToWithdraw)?;
+        if env.ledger().timestamp() < unbonding.release_at {
+            return Err(Error::StillCoolingDown);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Unbonding(user.clone()));
+        Self::tur_client(&env).transfer(
+            &env.current_contract_address(),
+            &user,
+            &unbonding.amount,
+        );
+        extend_instance(&env);
+
+        // Emit event
+        StakeWithdrawnEvent {
+            user,
+            amount: unbonding.amount,
+        }
+        .publish(&env);
+
+        Ok(unbonding.amount)
+    }
+
+    /// Get the stake of a user
+    pub fn get_stake(env: Env, user: Address) -> Stake {
+        let key = DataKey::Stake(user);
+        match env.storage().persistent().get(&key) {
+            Some(stake) => {
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&key, STAKE_LIFETIME_THRESHOLD, STAKE_BUMP_AMOUNT);
+                stake
+            }
+            None => Stake::default(),
+        }
+    }
+
+    /// Get the unstaked TUR of a user waiting for the cooldown, if any
+    pub fn get_unbonding(env: Env, user: Address) -> Option<Unbonding> {
+        extend_instance(&env);
+
+        let key = DataKey::Unbonding(user);
+        let unbonding = env.storage().persistent().get(&key);
+        if unbonding.is_some() {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, STAKE_LIFETIME_THRESHOLD, STAKE_BUMP_AMOUNT);
+        }
+        unbonding
+    }
+
+    /// Get the TUR staked by all users, unbonding TUR excluded
+    pub fn get_total_staked(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalStaked)
+            .unwrap_or(0)
+    }
+
+    /// Get the tier a user reached with its stake amount and duration
+    pub fn tier_of(env: Env, user: Address) -> Tier {
+        extend_instance(&env);
+
+        let stake = Self::get_stake(env.clone(), user);
+        let duration = env.ledger().timestamp().saturating_sub(stake.since);
+        Self::get_tier_config(env).tier(stake.amount, duration)
+    }
+
+    /// Replace the tier thresholds (only admin)
+    pub fn set_tier_config(env: Env, tiers: TierConfig) -> Result<(), Error> {
+        Self::require_admin(&env);
+
+        tiers.validate()?;
+        env.storage().instance().set(&DataKey::TierConfig, &tiers);
+        extend_instance(&env);
+
+        // Emit event
+        TierConfigUpdatedEvent {
+            silver_min_stake: tiers.silver.min_stake,
+            silver_min_seconds: tiers.silver.min_seconds,
+            gold_min_stake: tiers.gold.min_stake,
+            gold_min_seconds: tiers.gold.min_seconds,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the tier thresholds
+    pub fn get_tier_config(env: Env) -> TierConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TierConfig)
+            .unwrap()
+    }
+
+    /// Set how long unstaked TUR stays locked (only admin). Unbondings
+    /// already started keep their release time.
+    pub fn set_cooldown(env: Env, cooldown_seconds: u64) {
+        Self::require_admin(&env);
+
+        env.storage().instance().set(&DataKey::CooldownSeconds, &cooldown_seconds);
+        extend_instance(&env);
+
+        // Emit event
+        CooldownUpdatedEvent { cooldown_seconds }.publish(&env);
+    }
+
+    /// Get how long unstaked TUR stays locked, in seconds
+    pub fn get_cooldown(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CooldownSeconds)
+            .unwrap_or(0)
+    }
+
+    fn write_stake(env: &Env, user: &Address, stake: &Stake) {
+        let key = DataKey::Stake(user.clone());
+        if stake.amount == 0 {
+            env.storage().persistent().remove(&key);
+            return;
+        }
+
+        env.storage().persistent().set(&key, stake);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, STAKE_LIFETIME_THRESHOLD, STAKE_BUMP_AMOUNT);
+    }
+
+    fn add_total_staked(env: &Env, amount: i128) {
+        let total_staked = Self::get_total_staked(env.clone()) + amount;
+        env.storage().instance().set(&DataKey::TotalStaked, &total_staked);
+    }
+
+    fn tur_client(env: &Env) -> token::TokenClient<'_> {
+        let tur_contract: Address = env.storage()
+            .instance()
+            .get(&DataKey::TurTokenContract)
+            .unwrap();
+
+        token::TokenClient::new(env, &tur_contract)
+    }
+
+    fn require_admin(env: &Env) {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap();
+
+        admin.require_auth();
+    }
+}
+
+// Keep the contract instance alive while the pool is in use
+fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        testutils::{storage::Instance as _, Address as _, Ledger},
+        Env, String,
+    };
+    use tur_token::{TurToken, TurTokenClient};
+    use turista_interfaces::StakingTierClient;
+
+    const DAY: u64 = 86_400;
+    const COOLDOWN: u64 = 7 * DAY;
+
+    fn tiers() -> TierConfig {
+        TierConfig {
+            silver: TierThreshold {
+                min_stake: 1_000_0000000,
+                min_seconds: 7 * DAY,
+            },
+            gold: TierThreshold {
+                min_stake: 10_000_0000000,
+                min_seconds: 30 * DAY,
+            },
+        }
+    }
+
+    // Staking contract wired to a TUR token, with a funded traveler
+    struct Setup<'a> {
+        admin: Address,
+        client: StakingClient<'a>,
+        tur_client: TurTokenClient<'a>,
+        traveler: Address,
+    }
+
+    impl<'a> Setup<'a> {
+        fn new(env: &'a Env) -> Self {
+            env.mock_all_auths();
+            let admin = Address::generate(env);
+
+            let tur_contract_id = env.register(TurToken, ());
+            let tur_client = TurTokenClient::new(env, &tur_contract_id);
+            tur_client.initialize(
+                &admin,
+                &String::from_str(env, "Turista Token"),
+                &String::from_str(env, "TUR"),
+                &7,
+                &10_000_000_0000000,
+                &100_000_000_0000000,
+            );
+
+            let contract_id = env.register(Staking {}, ());
+            let client = StakingClient::new(env, &contract_id);
+            client.initialize(&admin, &tur_contract_id, &tiers(), &COOLDOWN);
+
+            let traveler = Address::generate(env);
+            tur_client.transfer(&admin, &traveler, &20_000_0000000);
+
+            Setup {
+                admin,
+                client,
+                tur_client,
+                traveler,
+            }
+        }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        let setup = Setup::new(&env);
+
+        assert_eq!(setup.client.get_tier_config(), tiers());
+        assert_eq!(setup.client.get_cooldown(), COOLDOWN);
+        assert_eq!(setup.client.tier_of(&setup.traveler), Tier::Standard);
+
+        // Gold cannot ask less than silver
+        let mut invalid = tiers();
+        invalid.gold.min_seconds = DAY;
+        assert_eq!(
+            setup.client.try_set_tier_config(&invalid),
+            Err(Ok(Error::InvalidTierConfig))
+        );
+
+        // Initialization cannot be repeated to swap the admin or the token
+        let attacker = Address::generate(&env);
+        let fake_token = Address::generate(&env);
+        assert_eq!(
+            setup.client.try_initialize(&attacker, &fake_token, &tiers(), &0),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+        assert_eq!(setup.client.get_cooldown(), COOLDOWN);
+
+        // Reads keep the instance alive
+        env.ledger().set_sequence_number(env.ledger().sequence() + 2 * DAY_IN_LEDGERS);
+        setup.client.tier_of(&setup.traveler);
+        env.as_contract(&setup.client.address, || {
+            assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+        });
+    }
+
+    #[test]
+    fn test_stake_amount_and_duration_set_the_tier() {
+        let env = Env::default();
+        let setup = Setup::new(&env);
+        let traveler = &setup.traveler;
+        let tier_client = StakingTierClient::new(&env, &setup.client.address);
+
+        assert_eq!(
+            setup.client.try_stake(traveler, &0),
+            Err(Ok(Error::InvalidAmount))
+        );
+
+        // The stake is locked in the contract
+        setup.client.stake(traveler, &1_000_0000000);
+        assert_eq!(setup.tur_client.balance(traveler), 19_000_0000000);
+        assert_eq!(setup.tur_client.balance(&setup.client.address), 1_000_0000000);
+        assert_eq!(setup.client.get_total_staked(), 1_000_0000000);
+
+        // Tiers need the stake to be held long enough
+        assert_eq!(tier_client.tier_of(traveler), Tier::Standard);
+        env.ledger().set_timestamp(7 * DAY);
+        assert_eq!(tier_client.tier_of(traveler), Tier::Silver);
+
+        // Adding to the stake averages its age
+        setup.client.stake(traveler, &9_000_0000000);
+        assert_eq!(
+            setup.client.get_stake(traveler),
+            Stake {
+                amount: 10_000_0000000,
+                since: 63 * DAY / 10,
+            }
+        );
+        assert_eq!(tier_client.tier_of(traveler), Tier::Standard);
+        env.ledger().set_timestamp(63 * DAY / 10 + 7 * DAY);
+        assert_eq!(tier_client.tier_of(traveler), Tier::Silver);
+        env.ledger().set_timestamp(63 * DAY / 10 + 30 * DAY);
+        assert_eq!(tier_client.tier_of(traveler), Tier::Gold);
+    }
+
+    #[test]
+    fn test_unbonding_cooldown() {
+        let env = Env::default();
+        let setup = Setup::new(&env);
+        let traveler = &setup.traveler;
+
+        setup.client.stake(traveler, &10_000_0000000);
+        env.ledger().set_timestamp(30 * DAY);
+        assert_eq!(setup.client.tier_of(traveler), Tier::Gold);
+
+        assert_eq!(
+            setup.client.try_unstake(traveler, &10_000_0000001),
+            Err(Ok(Error::InsufficientStake))
+        );
+        assert_eq!(
+            setup.client.try_withdraw(traveler),
+            Err(Ok(Error::NothingToWithdraw))
+        );
+
+        // Unbonding TUR no longer counts towards the tier
+        setup.client.unstake(traveler, &5_000_0000000);
+        assert_eq!(setup.client.tier_of(traveler), Tier::Silver);
+        assert_eq!(setup.client.get_total_staked(), 5_000_0000000);
+        assert_eq!(
+            setup.client.get_unbonding(traveler),
+            Some(Unbonding {
+                amount: 5_000_0000000,
+                release_at: 30 * DAY + COOLDOWN,
+            })
+        );
+
+        // and stays locked until the cooldown is over
+        env.ledger().set_timestamp(30 * DAY + COOLDOWN - 1);
+        assert_eq!(
+            setup.client.try_withdraw(traveler),
+            Err(Ok(Error::StillCoolingDown))
+        );
+        env.ledger().set_timestamp(30 * DAY + COOLDOWN);
+        assert_eq!(setup.client.withdraw(traveler), 5_000_0000000);
+        assert_eq!(setup.tur_client.balance(traveler), 15_000_0000000);
+        assert_eq!(setup.client.get_unbonding(traveler), None);
+        assert_eq!(
+            setup.client.try_withdraw(traveler),
+            Err(Ok(Error::NothingToWithdraw))
+        );
+    }
+
+    #[test]
+    fn test_points_expire_once_withdrawn() {
+        let env = Env::default();
+        let setup = Setup::new(&env);
+        let staking = &setup.client.address;
+
+        // Reward points expire after 10 days; the pool itself is exempt
+        setup.tur_client.set_points_lifetime(&setup.admin, &(10 * DAY));
+        assert_eq!(
+            setup.tur_client.try_set_expiry_exempt(&setup.traveler, staking, &true),
+            Err(Ok(tur_token::Error::Unauthorized))
+        );
+        setup.tur_client.set_expiry_exempt(&setup.admin, staking, &true);
+        assert!(setup.tur_client.is_expiry_exempt(staking));
+
+        // The pool keeps the lots of each depositor aside
+        let tourist = Address::generate(&env);
+        setup.tur_client.mint(&setup.admin, &tourist, &1_000_0000000);
+        setup.client.stake(&tourist, &1_000_0000000);
+        setup.client.stake(&setup.traveler, &1_000_0000000);
+        assert_eq!(setup.tur_client.get_lots(staking).len(), 0);
+        assert_eq!(setup.tur_client.get_held_lots(staking, &tourist).len(), 1);
+        assert_eq!(setup.tur_client.get_held_lots(staking, &setup.traveler).len(), 0);
+
+        // Points bought at day 29 are still valid when withdrawn
+        let late_tourist = Address::generate(&env);
+        env.ledger().set_timestamp(29 * DAY);
+        setup.tur_client.mint(&setup.admin, &late_tourist, &500_0000000);
+        setup.client.stake(&late_tourist, &500_0000000);
+
+        // Staked points stay in the pool past their expiry
+        env.ledger().set_timestamp(30 * DAY);
+        assert_eq!(setup.tur_client.balance(staking), 2_500_0000000);
+        assert_eq!(setup.tur_client.expire(staking), 0);
+
+        setup.client.unstake(&setup.traveler, &1_000_0000000);
+        setup.client.unstake(&tourist, &1_000_0000000);
+        setup.client.unstake(&late_tourist, &500_0000000);
+        env.ledger().set_timestamp(30 * DAY + COOLDOWN);
+        let supply = setup.tur_client.total_supply();
+
+        // Points that never expired come back in full
+        assert_eq!(setup.client.withdraw(&setup.traveler), 1_000_0000000);
+        assert_eq!(setup.tur_client.balance(&setup.traveler), 20_000_0000000);
+
+        // Expired points are burned on the way out instead of returned
+        setup.client.withdraw(&tourist);
+        assert_eq!(setup.tur_client.balance(&tourist), 0);
+        assert_eq!(setup.tur_client.total_supply(), supply - 1_000_0000000);
+        assert_eq!(setup.tur_client.get_held_lots(staking, &tourist).len(), 0);
+
+        // Points still valid keep their expiry date
+        setup.client.withdraw(&late_tourist);
+        assert_eq!(setup.tur_client.balance(&late_tourist), 500_0000000);
+        assert_eq!(setup.tur_client.get_lots(&late_tourist).len(), 1);
+        env.ledger().set_timestamp(40 * DAY);
+        assert_eq!(setup.tur_client.balance(&late_tourist), 0);
+
+        assert_eq!(setup.tur_client.balance(staking), 0);
+        assert_eq!(setup.client.get_total_staked(), 0);
+    }
+}
@@ -0,0 +1,51 @@
+use soroban_sdk::contracttype;
+use turista_interfaces::Tier;
+
+use crate::Error;
+
+// Stake needed for a tier: at least `min_stake` TUR locked for at least
+// `min_seconds`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierThreshold {
+    pub min_stake: i128,
+    pub min_seconds: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierConfig {
+    pub silver: TierThreshold,
+    pub gold: TierThreshold,
+}
+
+impl TierThreshold {
+    fn is_met(&self, amount: i128, duration: u64) -> bool {
+        amount >= self.min_stake && duration >= self.min_seconds
+    }
+}
+
+impl TierConfig {
+    /// Tiers need a positive stake, and gold asks at least as much as
+    /// silver on both counts
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.silver.min_stake <= 0
+            || self.gold.min_stake < self.silver.min_stake
+            || self.gold.min_seconds < self.silver.min_seconds
+        {
+            return Err(Error::InvalidTierConfig);
+        }
+        Ok(())
+    }
+
+    /// Highest tier met by `amount` staked for `duration` seconds
+    pub fn tier(&self, amount: i128, duration: u64) -> Tier {
+        if self.gold.is_met(amount, duration) {
+            Tier::Gold
+        } else if self.silver.is_met(amount, duration) {
+            Tier::Silver
+        } else {
+            Tier::Standard
+        }
+    }
+}
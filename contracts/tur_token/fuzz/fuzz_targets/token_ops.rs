@@ -0,0 +1,64 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tur_token::testutils::{Harness, Op};
+
+// Longest operation sequence run per input
+const MAX_OPS: usize = 64;
+
+#[derive(Arbitrary, Debug)]
+enum FuzzOp {
+    Mint { to: u8, amount: i128 },
+    Transfer { from: u8, to: u8, amount: i128 },
+    Burn { from: u8, amount: i128 },
+    Approve { owner: u8, spender: u8, amount: i128 },
+    TransferFrom { spender: u8, from: u8, to: u8, amount: i128 },
+    BurnFrom { spender: u8, from: u8, amount: i128 },
+}
+
+impl From<&FuzzOp> for Op {
+    fn from(op: &FuzzOp) -> Self {
+        match *op {
+            FuzzOp::Mint { to, amount } => Op::Mint { to, amount },
+            FuzzOp::Transfer { from, to, amount } => Op::Transfer { from, to, amount },
+            FuzzOp::Burn { from, amount } => Op::Burn { from, amount },
+            FuzzOp::Approve {
+                owner,
+                spender,
+                amount,
+            } => Op::Approve {
+                owner,
+                spender,
+                amount,
+            },
+            FuzzOp::TransferFrom {
+                spender,
+                from,
+                to,
+                amount,
+            } => Op::TransferFrom {
+                spender,
+                from,
+                to,
+                amount,
+            },
+            FuzzOp::BurnFrom {
+                spender,
+                from,
+                amount,
+            } => Op::BurnFrom {
+                spender,
+                from,
+                amount,
+            },
+        }
+    }
+}
+
+fuzz_target!(|ops: Vec<FuzzOp>| {
+    let mut harness = Harness::new();
+    for op in ops.iter().take(MAX_OPS) {
+        harness.apply(&op.into());
+    }
+});
@@ -0,0 +1,69 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{AllowanceValue, DataKey, Error};
+
+// Allowances live in temporary storage and are kept alive only until
+// their expiration ledger; an archived allowance reads as zero, like an
+// expired one.
+
+/// Allowance of `spender` over the tokens of `from`; expired allowances read as zero
+pub fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    let allowance: Option<AllowanceValue> = env.storage().temporary().get(&key);
+
+    match allowance {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+        Some(allowance) => AllowanceValue {
+            amount: 0,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+        None => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
+}
+
+pub fn write_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+) {
+    if amount > 0 && expiration_ledger < env.ledger().sequence() {
+        panic_with_error!(env, Error::InvalidExpirationLedger);
+    }
+
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    env.storage().temporary().set(
+        &key,
+        &AllowanceValue {
+            amount,
+            expiration_ledger,
+        },
+    );
+
+    // Keep the entry until it expires
+    if amount > 0 {
+        let live_for = expiration_ledger - env.ledger().sequence();
+        env.storage().temporary().extend_ttl(&key, live_for, live_for);
+    }
+}
+
+pub fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let allowance = read_allowance(env, from, spender);
+    if allowance.amount < amount {
+        panic_with_error!(env, Error::InsufficientAllowance);
+    }
+
+    if amount > 0 {
+        write_allowance(
+            env,
+            from,
+            spender,
+            allowance.amount - amount,
+            allowance.expiration_ledger,
+        );
+    }
+}
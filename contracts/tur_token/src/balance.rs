@@ -0,0 +1,92 @@
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+use crate::{
+    checkpoints::{self, Series},
+    delegation, lots, DataKey, Error, Lot, TurToken, BALANCE_BUMP_AMOUNT,
+    BALANCE_LIFETIME_THRESHOLD};
+
+// Balances live in persistent storage. Tokens deployed before that kept
+// them in instance storage; those are still read from there until the
+// balance is written again or migrated.
+
+pub fn read_balance(env: &Env, id: &Address) -> i128 {
+    let key = DataKey::Balance(id.clone());
+    if let Some(balance) = env.storage().persistent().get::<_, i128>(&key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        return balance;
+    }
+
+    env.storage().instance().get(&key).unwrap_or(0)
+}
+
+pub fn write_balance(env: &Env, id: &Address, balance: i128) {
+    let previous = read_balance(env, id);
+    checkpoints::record(env, &Series::Balance(id.clone()), previous, balance);
+    delegation::record_balance_change(env, id, balance - previous);
+
+    let key = DataKey::Balance(id.clone());
+    env.storage().persistent().set(&key, &balance);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+
+    // Drop the legacy entry so the instance stops carrying it
+    env.storage().instance().remove(&key);
+}
+
+/// Balance without the points held in expired lots, which stay in the
+/// stored balance until swept
+pub fn spendable_balance(env: &Env, id: &Address) -> i128 {
+    read_balance(env, id) - lots::expired_amount(env, id)
+}
+
+/// Debit an account and return the lots the amount was taken from, for
+/// the recipient `to` when the points move to another account. Its
+/// expired points are burned first.
+pub fn spend_balance(env: &Env, id: &Address, amount: i128, to: Option<&Address>) -> Vec<Lot> {
+    TurToken::burn_expired(env, id);
+    if read_balance(env, id) < amount {
+        panic_with_error!(env, Error::InsufficientBalance);
+    }
+
+    write_balance(env, id, read_balance(env, id) - amount);
+    lots::take(env, id, amount, to)
+}
+
+/// Credit an account with `amount` sent by `from`, carrying over the lots
+/// it came from. Expired points, including lots an exempt account held
+/// past their expiry, are burned afterwards.
+pub fn receive_balance(env: &Env, id: &Address, from: &Address, amount: i128, lots: &Vec<Lot>) {
+    let balance = read_balance(env, id)
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, Error::Overflow));
+
+    write_balance(env, id, balance);
+    lots::give(env, id, Some(from), lots);
+    TurToken::burn_expired(env, id);
+}
+
+/// Move the instance-stored balances of `holders` to persistent storage
+/// and return how many were moved
+pub fn migrate_balances(env: &Env, holders: &Vec<Address>) -> u32 {
+    let mut migrated = 0;
+
+    for holder in holders.iter() {
+        let key = DataKey::Balance(holder.clone());
+        let Some(legacy) = env.storage().instance().get::<_, i128>(&key) else {
+            continue;
+        };
+
+        // A persistent entry is always the most recent one
+        if !env.storage().persistent().has(&key) {
+            write_balance(env, &holder, legacy);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        migrated += 1;
+    }
+
+    migrated
+}
@@ -0,0 +1,166 @@
+use soroban_sdk::{contracttype, Address, Env, IntoVal, TryFromVal, Val};
+
+use crate::{DataKey, Error, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+// Checkpoints kept per account, for its balance and its votes, and for
+// the total supply. Older ones are overwritten, so storage stays bounded;
+// the supply changes with every mint and burn and keeps a longer history.
+const MAX_BALANCE_CHECKPOINTS: u32 = 256;
+const MAX_SUPPLY_CHECKPOINTS: u32 = 8_192;
+
+// Value at the end of `ledger`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub amount: i128,
+}
+
+// Checkpoints of a series form a ring: checkpoint `first + i` lives in
+// slot `(first + i) % capacity`, oldest first
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct History {
+    pub first: u32,
+    pub len: u32,
+}
+
+/// A value tracked over time
+pub enum Series {
+    Balance(Address),
+    Votes(Address),
+    Supply,
+}
+
+impl Series {
+    fn capacity(&self) -> u32 {
+        match self {
+            Series::Balance(_) | Series::Votes(_) => MAX_BALANCE_CHECKPOINTS,
+            Series::Supply => MAX_SUPPLY_CHECKPOINTS,
+        }
+    }
+
+    fn history_key(&self) -> DataKey {
+        match self {
+            Series::Balance(id) => DataKey::BalanceHistory(id.clone()),
+            Series::Votes(id) => DataKey::VotesHistory(id.clone()),
+            Series::Supply => DataKey::SupplyHistory,
+        }
+    }
+
+    fn checkpoint_key(&self, index: u32) -> DataKey {
+        let slot = index % self.capacity();
+        match self {
+            Series::Balance(id) => DataKey::BalanceCheckpoint(id.clone(), slot),
+            Series::Votes(id) => DataKey::VotesCheckpoint(id.clone(), slot),
+            Series::Supply => DataKey::SupplyCheckpoint(slot),
+        }
+    }
+}
+
+fn read<T: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<T> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    value
+}
+
+fn write<T: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &T) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+fn read_checkpoint(env: &Env, series: &Series, index: u32) -> Checkpoint {
+    read(env, &series.checkpoint_key(index)).unwrap()
+}
+
+/// Record that a value went from `previous` to `amount` in the current
+/// ledger. A value that predates the checkpoints is recorded as held
+/// since ledger 0.
+pub fn record(env: &Env, series: &Series, previous: i128, amount: i128) {
+    let ledger = env.ledger().sequence();
+    let mut history: History = match read(env, &series.history_key()) {
+        Some(history) => history,
+        None => {
+            if previous == amount {
+                return;
+            }
+            let mut history = History::default();
+            if previous != 0 {
+                let checkpoint = Checkpoint {
+                    ledger: 0,
+                    amount: previous,
+                };
+                push(env, series, &mut history, checkpoint);
+                write(env, &series.history_key(), &history);
+            }
+            history
+        }
+    };
+
+    if history.len > 0 {
+        let last_index = history.first + history.len - 1;
+        let last = read_checkpoint(env, series, last_index);
+        if last.amount == amount {
+            return;
+        }
+        // One checkpoint per ledger, holding the value at its end
+        if last.ledger == ledger {
+            let checkpoint = Checkpoint { ledger, amount };
+            write(env, &series.checkpoint_key(last_index), &checkpoint);
+            return;
+        }
+    }
+
+    push(env, series, &mut history, Checkpoint { ledger, amount });
+    write(env, &series.history_key(), &history);
+}
+
+// Append a checkpoint, overwriting the oldest one when the ring is full
+fn push(env: &Env, series: &Series, history: &mut History, checkpoint: Checkpoint) {
+    write(env, &series.checkpoint_key(history.first + history.len), &checkpoint);
+    if history.len == series.capacity() {
+        history.first += 1;
+    } else {
+        history.len += 1;
+    }
+}
+
+/// Value at the end of `ledger`, which must have closed. `current` is
+/// the value now, used when the series never changed since checkpoints
+/// were introduced.
+pub fn value_at(env: &Env, series: &Series, ledger: u32, current: i128) -> Result<i128, Error> {
+    if ledger >= env.ledger().sequence() {
+        return Err(Error::FutureLedger);
+    }
+
+    let Some(history) = read::<History>(env, &series.history_key()) else {
+        return Ok(current);
+    };
+
+    // Binary search for the last checkpoint at or before `ledger`
+    let (mut low, mut high) = (history.first, history.first + history.len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if read_checkpoint(env, series, mid).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if low > history.first {
+        return Ok(read_checkpoint(env, series, low - 1).amount);
+    }
+    // Before the first checkpoint the value was 0, unless older
+    // checkpoints were overwritten
+    if history.first > 0 {
+        return Err(Error::CheckpointPruned);
+    }
+    Ok(0)
+}
@@ -0,0 +1,99 @@
+use soroban_sdk::{contracttype, panic_with_error, Address, Env};
+
+use crate::{DataKey, Error, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+// Who holders may send tokens to. Mints, burns and clawbacks are not
+// transfers and are allowed under every policy.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TransferPolicy {
+    Transferable = 0,  // anyone
+    AllowlistOnly = 1, // allowlisted addresses only, e.g. merchants
+    Locked = 2,        // nobody
+}
+
+// Frozen flags live next to the balances, in persistent storage, and are
+// kept alive as long as a balance would be
+
+pub fn is_frozen(env: &Env, account: &Address) -> bool {
+    let key = DataKey::Frozen(account.clone());
+    let frozen = env.storage().persistent().get(&key).unwrap_or(false);
+    if frozen {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    frozen
+}
+
+pub fn write_frozen(env: &Env, account: &Address, frozen: bool) {
+    let key = DataKey::Frozen(account.clone());
+    if frozen {
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn check_not_frozen(env: &Env, account: &Address) {
+    if is_frozen(env, account) {
+        panic_with_error!(env, Error::AccountFrozen);
+    }
+}
+
+pub fn read_policy(env: &Env) -> TransferPolicy {
+    env.storage()
+        .instance()
+        .get(&DataKey::TransferPolicy)
+        .unwrap_or(TransferPolicy::Transferable)
+}
+
+// Allowlist entries are per account too and are stored the same way
+
+pub fn is_allowlisted(env: &Env, account: &Address) -> bool {
+    let key = DataKey::Allowlisted(account.clone());
+    let allowed = env.storage().persistent().get(&key).unwrap_or(false);
+    if allowed {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    allowed
+}
+
+pub fn write_allowlisted(env: &Env, account: &Address, allowed: bool) {
+    let key = DataKey::Allowlisted(account.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Panic unless the transfer policy lets holders send tokens to `to`
+pub fn check_transfer_allowed(env: &Env, to: &Address) {
+    match read_policy(env) {
+        TransferPolicy::Transferable => {}
+        TransferPolicy::AllowlistOnly => {
+            if !is_allowlisted(env, to) {
+                panic_with_error!(env, Error::RecipientNotAllowlisted);
+            }
+        }
+        TransferPolicy::Locked => panic_with_error!(env, Error::TransfersLocked),
+    }
+}
+
+/// Account receiving clawed back tokens, the initial admin unless set
+pub fn read_treasury(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Treasury)
+        .unwrap_or_else(|| env.storage().instance().get(&DataKey::Admin).unwrap())
+}
@@ -0,0 +1,126 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    balance,
+    checkpoints::{self, Series},
+    DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD,
+};
+
+// Holders vote with their own balance unless they delegate it. Only
+// delegated weight is stored: the votes of an account are its balance
+// when it does not delegate, plus the balances delegated to it. Every
+// change of an account's votes is checkpointed for `get_past_votes`.
+
+pub fn read_delegate(env: &Env, delegator: &Address) -> Option<Address> {
+    let key = DataKey::Delegate(delegator.clone());
+    let delegate = env.storage().persistent().get(&key);
+    if delegate.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    delegate
+}
+
+fn write_delegate(env: &Env, delegator: &Address, delegate: Option<&Address>) {
+    let key = DataKey::Delegate(delegator.clone());
+    match delegate {
+        Some(delegate) => {
+            env.storage().persistent().set(&key, delegate);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+fn read_delegated_votes(env: &Env, delegate: &Address) -> i128 {
+    let key = DataKey::DelegatedVotes(delegate.clone());
+    match env.storage().persistent().get(&key) {
+        Some(votes) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+            votes
+        }
+        None => 0,
+    }
+}
+
+fn add_delegated_votes(env: &Env, delegate: &Address, amount: i128) {
+    let key = DataKey::DelegatedVotes(delegate.clone());
+    let votes = read_delegated_votes(env, delegate) + amount;
+    if votes == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+
+    env.storage().persistent().set(&key, &votes);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Voting weight of an account
+pub fn votes(env: &Env, account: &Address) -> i128 {
+    let own = match read_delegate(env, account) {
+        Some(_) => 0,
+        None => balance::read_balance(env, account),
+    };
+    own + read_delegated_votes(env, account)
+}
+
+// Checkpoint the votes of `account` after a change from `previous`
+fn record_votes(env: &Env, account: &Address, previous: i128) {
+    let votes = votes(env, account);
+    checkpoints::record(env, &Series::Votes(account.clone()), previous, votes);
+}
+
+/// Move the weight of `delegator` to `delegate`; delegating to oneself
+/// takes it back. Returns the previous delegate.
+pub fn delegate(env: &Env, delegator: &Address, delegate: &Address) -> Option<Address> {
+    let previous = read_delegate(env, delegator);
+    let delegator_votes = votes(env, delegator);
+    let previous_votes = previous.as_ref().map(|previous| votes(env, previous));
+    let delegate_votes = votes(env, delegate);
+
+    let weight = balance::read_balance(env, delegator);
+    if let Some(previous) = &previous {
+        add_delegated_votes(env, previous, -weight);
+    }
+
+    if delegate == delegator {
+        write_delegate(env, delegator, None);
+    } else {
+        add_delegated_votes(env, delegate, weight);
+        write_delegate(env, delegator, Some(delegate));
+    }
+
+    record_votes(env, delegator, delegator_votes);
+    if let (Some(previous), Some(previous_votes)) = (&previous, previous_votes) {
+        record_votes(env, previous, previous_votes);
+    }
+    record_votes(env, delegate, delegate_votes);
+    previous
+}
+
+/// Follow a balance change of `holder` in its own votes, or in those of
+/// its delegate. Called before the new balance is written.
+pub fn record_balance_change(env: &Env, holder: &Address, change: i128) {
+    if change == 0 {
+        return;
+    }
+    match read_delegate(env, holder) {
+        Some(delegate) => {
+            let previous = votes(env, &delegate);
+            add_delegated_votes(env, &delegate, change);
+            record_votes(env, &delegate, previous);
+        }
+        None => {
+            let previous = votes(env, holder);
+            let series = Series::Votes(holder.clone());
+            checkpoints::record(env, &series, previous, previous + change);
+        }
+    }
+}
@@ -7,4 +7,22 @@ pub enum Error {
     InsufficientBalance = 200,
     UnauthorizedBurn = 201,
     Unauthorized = 202,
+    NegativeAmount = 203,
+    InsufficientAllowance = 204,
+    InvalidExpirationLedger = 205,
+    Overflow = 206,
+    SupplyCapExceeded = 207,
+    MinterQuotaExceeded = 208,
+    MinterRateLimited = 209,
+    InvalidMinterLimits = 210,
+    Paused = 211,
+    AccountFrozen = 212,
+    TransfersLocked = 213,
+    RecipientNotAllowlisted = 214,
+    FutureLedger = 215,
+    CheckpointPruned = 216,
+    InvalidPermitNonce = 217,
+    InvalidPermitSigner = 218,
+    InitialAdminRole = 219,
+    AlreadyInitialized = 220,
 }
@@ -1,21 +1,90 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, symbol_short};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error,
+    token::{self, TokenInterface as _},
+    Address, Bytes, BytesN, Env, String, Vec,
+};
+use soroban_token_sdk::TokenUtils;
+use turista_events::{
+    AccountFrozenEvent, AllowlistUpdatedEvent, BalancesMigratedEvent, ClawbackEvent, ContractEvent,
+    ContractUpgradedEvent, DelegateChangedEvent, ExpiryExemptionUpdatedEvent, MinterLimitsUpdatedEvent, MinterUpdatedEvent, PausedEvent,
+    PointsExpiredEvent, PointsLifetimeUpdatedEvent, RoleUpdatedEvent, TokenInitializedEvent,
+    TransferPolicyUpdatedEvent, TreasuryUpdatedEvent,
+};
+mod allowance;
+mod balance;
+mod checkpoints;
+mod compliance;
+mod delegation;
 mod error;
+mod lots;
+mod minter;
+mod permit;
+mod roles;
+pub mod testutils;
+pub use checkpoints::Checkpoint;
+pub use compliance::TransferPolicy;
 pub use error::Error;
+pub use lots::Lot;
+pub use minter::{MinterLimits, MinterUsage};
+pub use permit::PermitPayload;
+pub use roles::Role;
+
+// Ledgers closed in a day, at about 5 seconds per ledger
+pub const DAY_IN_LEDGERS: u32 = 17280;
+
+// The instance (metadata, supply, minters) is bumped to a week whenever
+// less than six days are left
+pub const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+// Balances are bumped to a month whenever they are read or written
+pub const BALANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
 // Storage keys
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
-    Balance(Address),
+    Balance(Address), // persistent (instance before the migration)
     TotalSupply,
     Name,
     Symbol,
     Decimals,
-    Admin,
+    Admin, // initial admin, always holds every role
+    Role(Role, Address), // persistent, (role, account) -> bool
+    Paused,
+    Frozen(Address), // persistent, Address -> bool
+    Treasury, // receives clawed back tokens
+    TransferPolicy,
+    Allowlisted(Address), // persistent, Address -> bool, recipients allowed under AllowlistOnly
+    PointsLifetime, // seconds minted points stay usable, 0 when they never expire
+    Lots(Address), // persistent, Address -> Vec<Lot>
+    HeldLots(Address, Address), // persistent, (exempt holder, depositor) -> Vec<Lot>
+    ExpiryExempt(Address), // persistent, Address -> bool, holders whose points do not expire while held
+    BalanceHistory(Address), // persistent, Address -> History
+    BalanceCheckpoint(Address, u32), // persistent, (Address, slot) -> Checkpoint
+    SupplyHistory, // persistent
+    SupplyCheckpoint(u32), // persistent, slot -> Checkpoint
+    Delegate(Address), // persistent, delegator -> delegate
+    DelegatedVotes(Address), // persistent, delegate -> sum of delegated balances
+    VotesHistory(Address), // persistent, Address -> History
+    VotesCheckpoint(Address, u32), // persistent, (Address, slot) -> Checkpoint
+    PermitNonce(Address), // persistent, owner -> nonce of its next permit
+    MaxSupply, // set once at initialization
     AuthorizedMinter(Address),
-    Allowance(Address, Address), // (owner, spender) -> amount
+    MinterLimits(Address), // Address -> MinterLimits
+    MinterUsage(Address), // Address -> MinterUsage
+    Allowance(Address, Address), // temporary, (owner, spender) -> AllowanceValue
+}
+
+// Allowance usable up to and including `expiration_ledger`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
 }
 
 
@@ -24,7 +93,9 @@ pub struct TurToken;
 
 #[contractimpl]
 impl TurToken {
-    /// Initialize the token with name, symbol, decimals, and initial supply
+    /// Initialize the token with name, symbol, decimals, initial supply and
+    /// the cap on the total supply, which cannot be changed afterwards.
+    /// Only callable once, by the admin.
     pub fn initialize(
         env: Env,
         admin: Address,
@@ -32,46 +103,46 @@ impl TurToken {
         symbol: String,
         decimals: u32,
         initial_supply: i128,
-    ) {
+        max_supply: i128,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        // Require admin authentication
+        admin.require_auth();
+
+        // Validate supply
+        if initial_supply < 0 {
+            return Err(Error::NegativeAmount);
+        }
+        if initial_supply > max_supply {
+            return Err(Error::SupplyCapExceeded);
+        }
+
         // Set token metadata
         env.storage().instance().set(&DataKey::Name, &name);
         env.storage().instance().set(&DataKey::Symbol, &symbol);
         env.storage().instance().set(&DataKey::Decimals, &decimals);
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::TotalSupply, &initial_supply);
+        env.storage().instance().set(&DataKey::MaxSupply, &max_supply);
+        Self::write_total_supply(&env, initial_supply);
 
         // Mint initial supply to admin
-        env.storage().instance().set(&DataKey::Balance(admin.clone()), &initial_supply);
+        balance::write_balance(&env, &admin, initial_supply);
+        extend_instance(&env);
 
         // Emit event
-        env.events().publish(
-            (symbol_short!("init"), admin),
+        TokenInitializedEvent {
+            admin,
+            name,
+            symbol,
+            decimals,
             initial_supply,
-        );
-    }
-
-    /// Get token name
-    pub fn name(env: Env) -> String {
-        env.storage()
-            .instance()
-            .get(&DataKey::Name)
-            .unwrap_or(String::from_str(&env, "Turistas Token"))
-    }
-
-    /// Get token symbol
-    pub fn symbol(env: Env) -> String {
-        env.storage()
-            .instance()
-            .get(&DataKey::Symbol)
-            .unwrap_or(String::from_str(&env, "TUR"))
-    }
+        }
+        .publish(&env);
 
-    /// Get token decimals
-    pub fn decimals(env: Env) -> u32 {
-        env.storage()
-            .instance()
-            .get(&DataKey::Decimals)
-            .unwrap_or(7)
+        Ok(())
     }
 
     /// Get total supply
@@ -82,353 +153,941 @@ impl TurToken {
             .unwrap_or(0)
     }
 
-    /// Get balance of an address
-    pub fn balance(env: Env, id: Address) -> i128 {
-        env.storage()
-            .instance()
-            .get(&DataKey::Balance(id))
-            .unwrap_or(0)
+    /// Get the total supply at the end of a past ledger
+    pub fn total_supply_at(env: Env, ledger: u32) -> Result<i128, Error> {
+        let current = Self::total_supply(env.clone());
+        checkpoints::value_at(&env, &checkpoints::Series::Supply, ledger, current)
     }
 
-    /// Transfer tokens from one address to another
-    pub fn transfer(
+    /// Get the balance of an address at the end of a past ledger, expired
+    /// points included until they are swept
+    pub fn balance_at(env: Env, id: Address, ledger: u32) -> Result<i128, Error> {
+        let current = balance::read_balance(&env, &id);
+        checkpoints::value_at(&env, &checkpoints::Series::Balance(id), ledger, current)
+    }
+
+    /// Approve `spender` on behalf of `owner` with a signature instead of
+    /// the owner's authorization, so anyone can submit it. `signature` is
+    /// the owner's ed25519 signature of `permit_message`, and `nonce` must
+    /// be the owner's `permit_nonce`.
+    ///
+    /// The signature is checked against the ed25519 key the owner's
+    /// G-address is made of, not against the account's signers: extra
+    /// signers, thresholds and a disabled master key are ignored. Whoever
+    /// holds that key can sign permits even after the account moved to
+    /// multisig or rotated its key, so such accounts must not rely on
+    /// permits.
+    pub fn permit(
         env: Env,
-        from: Address,
-        to: Address,
+        owner: Address,
+        spender: Address,
         amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        signature: BytesN<64>,
     ) -> Result<(), Error> {
-        // Require authentication
-        from.require_auth();
-
-        // Validate amount
         if amount < 0 {
-            return Err(Error::InsufficientBalance);
-        }
-
-        // Get balances
-        let from_balance = Self::balance(env.clone(), from.clone());
-        let to_balance = Self::balance(env.clone(), to.clone());
-
-        // Check sufficient balance
-        if from_balance < amount {
-            return Err(Error::InsufficientBalance);
+            return Err(Error::NegativeAmount);
         }
+        permit::verify(
+            &env,
+            &owner,
+            &spender,
+            amount,
+            expiration_ledger,
+            nonce,
+            &signature,
+        )?;
 
-        // Update balances
-        let new_from_balance = from_balance
-            .checked_sub(amount)
-            .ok_or(Error::InsufficientBalance)?;
-        let new_to_balance = to_balance
-            .checked_add(amount)
-            .ok_or(Error::InsufficientBalance)?;
-
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(from.clone()), &new_from_balance);
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(to.clone()), &new_to_balance);
+        allowance::write_allowance(&env, &owner, &spender, amount, expiration_ledger);
+        extend_instance(&env);
 
         // Emit event
-        env.events().publish(
-            (symbol_short!("transfer"), from, to),
-            amount,
-        );
+        TokenUtils::new(&env)
+            .events()
+            .approve(owner, spender, amount, expiration_ledger);
 
         Ok(())
     }
 
-    /// Burn tokens from an address
-    pub fn burn(
+    /// Get the nonce the next permit of an owner must carry
+    pub fn permit_nonce(env: Env, owner: Address) -> u64 {
+        permit::read_nonce(&env, &owner)
+    }
+
+    /// Get the bytes an owner signs for `permit`: the XDR of a
+    /// `PermitPayload` bound to this network and token
+    pub fn permit_message(
         env: Env,
-        from: Address,
+        owner: Address,
+        spender: Address,
         amount: i128,
-    ) -> Result<(), Error> {
-        // Require authentication
-        from.require_auth();
+        expiration_ledger: u32,
+        nonce: u64,
+    ) -> Bytes {
+        permit::message(&env, &owner, &spender, amount, expiration_ledger, nonce)
+    }
 
-        // Validate amount
-        if amount < 0 {
-            return Err(Error::InsufficientBalance);
-        }
+    /// Delegate the voting weight of `delegator` to `delegate`, or take it
+    /// back by delegating to oneself
+    pub fn delegate(env: Env, delegator: Address, delegate: Address) {
+        // Require delegator authentication
+        delegator.require_auth();
 
-        // Get balance
-        let balance = Self::balance(env.clone(), from.clone());
+        let previous = delegation::delegate(&env, &delegator, &delegate);
+        extend_instance(&env);
 
-        // Check sufficient balance
-        if balance < amount {
-            return Err(Error::InsufficientBalance);
+        // Emit event
+        DelegateChangedEvent {
+            delegator,
+            from_delegate: previous,
+            to_delegate: delegate,
         }
+        .publish(&env);
+    }
 
-        // Update balance
-        let new_balance = balance
-            .checked_sub(amount)
-            .ok_or(Error::InsufficientBalance)?;
+    /// Get the account an address delegates its voting weight to, if any
+    pub fn delegates(env: Env, account: Address) -> Option<Address> {
+        delegation::read_delegate(&env, &account)
+    }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(from.clone()), &new_balance);
+    /// Get the voting weight of an account: its balance unless delegated,
+    /// plus the balances delegated to it. Expired points count until they
+    /// are swept.
+    pub fn get_votes(env: Env, account: Address) -> i128 {
+        delegation::votes(&env, &account)
+    }
 
-        // Update total supply
-        let total_supply = Self::total_supply(env.clone());
-        let new_total_supply = total_supply
-            .checked_sub(amount)
-            .ok_or(Error::InsufficientBalance)?;
+    /// Get the voting weight of an account at the end of a past ledger
+    pub fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, Error> {
+        let current = delegation::votes(&env, &account);
+        checkpoints::value_at(&env, &checkpoints::Series::Votes(account), ledger, current)
+    }
 
+    /// Get the cap on the total supply. Tokens initialized before the cap
+    /// existed are uncapped.
+    pub fn max_supply(env: Env) -> i128 {
         env.storage()
             .instance()
-            .set(&DataKey::TotalSupply, &new_total_supply);
-
-        // Emit event
-        env.events().publish(
-            (symbol_short!("burn"), from),
-            amount,
-        );
+            .get(&DataKey::MaxSupply)
+            .unwrap_or(i128::MAX)
+    }
 
-        Ok(())
+    /// Get the amount that can still be minted before reaching the cap.
+    /// Burned tokens can be minted again.
+    pub fn remaining_mintable(env: Env) -> i128 {
+        Self::max_supply(env.clone()) - Self::total_supply(env)
     }
 
-    /// Mint new tokens (only admin)
+    /// Mint new tokens (admin role)
     pub fn mint(
         env: Env,
+        caller: Address,
         to: Address,
         amount: i128,
     ) -> Result<(), Error> {
-        // Get admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .unwrap();
+        // Require admin role
+        roles::require_role(&env, Role::Admin, &caller)?;
 
-        // Require admin authentication
-        admin.require_auth();
-
-        Self::internal_mint(env, to, amount)
+        Self::internal_mint(env, caller, to, amount)
     }
 
     /// Internal mint function (no auth required)
     fn internal_mint(
         env: Env,
+        minter: Address,
         to: Address,
         amount: i128,
     ) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) {
+            return Err(Error::Paused);
+        }
+
         // Validate amount
         if amount < 0 {
-            return Err(Error::InsufficientBalance);
+            return Err(Error::NegativeAmount);
         }
 
-        // Get balance
-        let balance = Self::balance(env.clone(), to.clone());
+        // Get balance, without the points that expired
+        Self::burn_expired(&env, &to);
+        let balance = balance::read_balance(&env, &to);
 
         // Update balance
         let new_balance = balance
             .checked_add(amount)
-            .ok_or(Error::InsufficientBalance)?;
-
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(to.clone()), &new_balance);
+            .ok_or(Error::Overflow)?;
 
         // Update total supply
         let total_supply = Self::total_supply(env.clone());
         let new_total_supply = total_supply
             .checked_add(amount)
-            .ok_or(Error::InsufficientBalance)?;
+            .ok_or(Error::Overflow)?;
+        if new_total_supply > Self::max_supply(env.clone()) {
+            return Err(Error::SupplyCapExceeded);
+        }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalSupply, &new_total_supply);
+        balance::write_balance(&env, &to, new_balance);
+        lots::record_mint(&env, &to, amount);
+        Self::write_total_supply(&env, new_total_supply);
+
+        extend_instance(&env);
 
         // Emit event
-        env.events().publish(
-            (symbol_short!("mint"), to),
-            amount,
-        );
+        TokenUtils::new(&env).events().mint(minter, to, amount);
 
         Ok(())
     }
 
-    /// Mint tokens by authorized minter (no admin auth required)
+    /// Mint tokens by authorized minter (no admin auth required), within
+    /// the minter's quota and rate limit. The minter must authorize the
+    /// call, which a contract does by invoking it directly.
     pub fn mint_by_authorized(
         env: Env,
         minter: Address,
         to: Address,
         amount: i128,
     ) -> Result<(), Error> {
+        // Require minter authentication
+        minter.require_auth();
+
         // Check if minter is authorized
         let is_authorized: bool = env.storage()
             .instance()
-            .get(&DataKey::AuthorizedMinter(minter))
+            .get(&DataKey::AuthorizedMinter(minter.clone()))
             .unwrap_or(false);
 
         if !is_authorized {
             return Err(Error::Unauthorized);
         }
 
-        Self::internal_mint(env, to, amount)
+        // Count the mint against the minter's limits
+        minter::record_mint(&env, &minter, amount)?;
+
+        Self::internal_mint(env, minter, to, amount)
     }
 
-    /// Authorize a contract to mint tokens (only admin)
+    /// Authorize a contract to mint tokens (minter manager role)
     pub fn set_authorized_minter(
         env: Env,
+        caller: Address,
         minter: Address,
         authorized: bool,
     ) -> Result<(), Error> {
-        // Get admin
-        let admin: Address = env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .unwrap();
-
-        // Require admin authentication
-        admin.require_auth();
+        // Require minter manager role
+        roles::require_role(&env, Role::MinterManager, &caller)?;
 
         // Set authorization
         env.storage()
             .instance()
             .set(&DataKey::AuthorizedMinter(minter.clone()), &authorized);
 
+        extend_instance(&env);
+
         // Emit event
-        env.events().publish(
-            (symbol_short!("auth_mnt"), minter, authorized),
-            (),
-        );
+        MinterUpdatedEvent { minter, authorized }.publish(&env);
 
         Ok(())
     }
 
-    /// Approve a spender to burn tokens on behalf of the owner
-    pub fn approve(
+    /// Set the quota and rate limit of a minter (minter manager role).
+    /// Minters cannot mint until their limits are set.
+    pub fn set_minter_limits(
         env: Env,
-        owner: Address,
-        spender: Address,
-        amount: i128,
+        caller: Address,
+        minter: Address,
+        limits: MinterLimits,
     ) -> Result<(), Error> {
-        // Require owner authentication
-        owner.require_auth();
+        // Require minter manager role
+        roles::require_role(&env, Role::MinterManager, &caller)?;
 
-        // Validate amount
-        if amount < 0 {
-            return Err(Error::InsufficientBalance);
+        limits.validate()?;
+        minter::write_limits(&env, &minter, &limits);
+
+        extend_instance(&env);
+
+        // Emit event
+        MinterLimitsUpdatedEvent {
+            minter,
+            quota: limits.quota,
+            rate_limit: limits.rate_limit,
+            period_seconds: limits.period_seconds,
         }
+        .publish(&env);
 
-        // Set allowance
-        env.storage()
+        Ok(())
+    }
+
+    /// Get the quota and rate limit of a minter
+    pub fn get_minter_limits(env: Env, minter: Address) -> Option<MinterLimits> {
+        minter::read_limits(&env, &minter)
+    }
+
+    /// Get the amount minted by a minter
+    pub fn get_minter_usage(env: Env, minter: Address) -> MinterUsage {
+        minter::read_usage(&env, &minter)
+    }
+
+    /// Get the amount a minter can mint right now, zero if not authorized
+    pub fn remaining_minter_allowance(env: Env, minter: Address) -> i128 {
+        let is_authorized: bool = env.storage()
             .instance()
-            .set(&DataKey::Allowance(owner.clone(), spender.clone()), &amount);
+            .get(&DataKey::AuthorizedMinter(minter.clone()))
+            .unwrap_or(false);
+
+        if !is_authorized {
+            return 0;
+        }
+        minter::remaining(&env, &minter)
+    }
+
+    /// Replace the contract code with an uploaded Wasm (admin role). Storage
+    /// is kept, so tokens deployed before balances moved to persistent
+    /// storage can be upgraded and then call `migrate_balances`.
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        // Require admin role
+        roles::require_role(&env, Role::Admin, &caller)?;
+
+        extend_instance(&env);
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
 
         // Emit event
-        env.events().publish(
-            (symbol_short!("approve"), owner, spender),
-            amount,
-        );
+        ContractUpgradedEvent { new_wasm_hash }.publish(&env);
 
         Ok(())
     }
 
-    /// Get allowance amount
-    pub fn allowance(
+    /// Move balances still held in instance storage to persistent storage
+    /// (admin role). Returns the number of balances moved.
+    pub fn migrate_balances(
         env: Env,
-        owner: Address,
-        spender: Address,
-    ) -> i128 {
+        caller: Address,
+        holders: Vec<Address>,
+    ) -> Result<u32, Error> {
+        // Require admin role
+        roles::require_role(&env, Role::Admin, &caller)?;
+
+        let count = balance::migrate_balances(&env, &holders);
+        extend_instance(&env);
+
+        // Emit event
+        BalancesMigratedEvent { count }.publish(&env);
+
+        Ok(count)
+    }
+
+    /// Grant a role to an account (admin role)
+    pub fn grant_role(
+        env: Env,
+        caller: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), Error> {
+        // Require admin role
+        roles::require_role(&env, Role::Admin, &caller)?;
+
+        Self::update_role(&env, role, account, true);
+        Ok(())
+    }
+
+    /// Revoke a role from an account (admin role). The initial admin
+    /// always holds every role, so its roles cannot be revoked.
+    pub fn revoke_role(
+        env: Env,
+        caller: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), Error> {
+        // Require admin role
+        roles::require_role(&env, Role::Admin, &caller)?;
+
+        if roles::is_initial_admin(&env, &account) {
+            return Err(Error::InitialAdminRole);
+        }
+
+        Self::update_role(&env, role, account, false);
+        Ok(())
+    }
+
+    /// Give up a role held by the caller. The initial admin cannot give up
+    /// its roles.
+    pub fn renounce_role(env: Env, account: Address, role: Role) -> Result<(), Error> {
+        // Require authentication
+        account.require_auth();
+
+        if roles::is_initial_admin(&env, &account) {
+            return Err(Error::InitialAdminRole);
+        }
+
+        Self::update_role(&env, role, account, false);
+        Ok(())
+    }
+
+    /// Check whether an account holds a role, directly or as an admin
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        roles::has_role(&env, role, &account)
+    }
+
+    /// Stop transfers, burns and mints (pauser role)
+    pub fn pause(env: Env, caller: Address) -> Result<(), Error> {
+        // Require pauser role
+        roles::require_role(&env, Role::Pauser, &caller)?;
+
+        Self::set_paused(&env, caller, true);
+        Ok(())
+    }
+
+    /// Resume transfers, burns and mints (pauser role)
+    pub fn unpause(env: Env, caller: Address) -> Result<(), Error> {
+        // Require pauser role
+        roles::require_role(&env, Role::Pauser, &caller)?;
+
+        Self::set_paused(&env, caller, false);
+        Ok(())
+    }
+
+    /// Check whether the token is paused
+    pub fn is_paused(env: Env) -> bool {
         env.storage()
             .instance()
-            .get(&DataKey::Allowance(owner, spender))
-            .unwrap_or(0)
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
     }
 
-    /// Burn tokens from an address using allowance
-    pub fn burn_from(
+    /// Block an account from transferring and burning its tokens
+    /// (compliance officer role)
+    pub fn freeze(env: Env, caller: Address, account: Address) -> Result<(), Error> {
+        // Require compliance officer role
+        roles::require_role(&env, Role::ComplianceOfficer, &caller)?;
+
+        Self::set_frozen(&env, account, true);
+        Ok(())
+    }
+
+    /// Lift the freeze of an account (compliance officer role)
+    pub fn unfreeze(env: Env, caller: Address, account: Address) -> Result<(), Error> {
+        // Require compliance officer role
+        roles::require_role(&env, Role::ComplianceOfficer, &caller)?;
+
+        Self::set_frozen(&env, account, false);
+        Ok(())
+    }
+
+    /// Check whether an account is frozen
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        compliance::is_frozen(&env, &account)
+    }
+
+    /// Move tokens of an account to the treasury, frozen or not
+    /// (compliance officer role)
+    pub fn clawback(
         env: Env,
-        spender: Address,
+        caller: Address,
         from: Address,
         amount: i128,
     ) -> Result<(), Error> {
-        // Require spender authentication
-        spender.require_auth();
+        // Require compliance officer role
+        roles::require_role(&env, Role::ComplianceOfficer, &caller)?;
 
-        // Validate amount
         if amount < 0 {
-            return Err(Error::InsufficientBalance);
+            return Err(Error::NegativeAmount);
         }
-
-        // Check allowance
-        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
-        if allowance < amount {
+        if balance::spendable_balance(&env, &from) < amount {
             return Err(Error::InsufficientBalance);
         }
 
-        // Get balance
-        let balance = Self::balance(env.clone(), from.clone());
-
-        // Check sufficient balance
-        if balance < amount {
-            return Err(Error::InsufficientBalance);
+        let treasury = compliance::read_treasury(&env);
+        let lots = balance::spend_balance(&env, &from, amount, Some(&treasury));
+        balance::receive_balance(&env, &treasury, &from, amount, &lots);
+        extend_instance(&env);
+
+        // Emit events; the standard transfer keeps balance indexers in sync
+        TokenUtils::new(&env)
+            .events()
+            .transfer(from.clone(), treasury.clone(), amount);
+        ClawbackEvent {
+            caller,
+            from,
+            treasury,
+            amount,
         }
+        .publish(&env);
 
-        // Update balance
-        let new_balance = balance
-            .checked_sub(amount)
-            .ok_or(Error::InsufficientBalance)?;
+        Ok(())
+    }
+
+    /// Set who holders may transfer tokens to (compliance officer role)
+    pub fn set_transfer_policy(
+        env: Env,
+        caller: Address,
+        policy: TransferPolicy,
+    ) -> Result<(), Error> {
+        // Require compliance officer role
+        roles::require_role(&env, Role::ComplianceOfficer, &caller)?;
 
         env.storage()
             .instance()
-            .set(&DataKey::Balance(from.clone()), &new_balance);
+            .set(&DataKey::TransferPolicy, &policy);
+        extend_instance(&env);
 
-        // Update total supply
-        let total_supply = Self::total_supply(env.clone());
-        let new_total_supply = total_supply
-            .checked_sub(amount)
-            .ok_or(Error::InsufficientBalance)?;
+        // Emit event
+        TransferPolicyUpdatedEvent {
+            policy: policy as u32,
+        }
+        .publish(&env);
 
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalSupply, &new_total_supply);
+        Ok(())
+    }
 
-        // Update allowance
-        let new_allowance = allowance
-            .checked_sub(amount)
-            .ok_or(Error::InsufficientBalance)?;
+    /// Get the transfer policy, `Transferable` unless set
+    pub fn transfer_policy(env: Env) -> TransferPolicy {
+        compliance::read_policy(&env)
+    }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Allowance(from.clone(), spender.clone()), &new_allowance);
+    /// Allow or stop transfers to an account under the `AllowlistOnly`
+    /// policy (compliance officer role)
+    pub fn set_allowlisted(
+        env: Env,
+        caller: Address,
+        account: Address,
+        allowed: bool,
+    ) -> Result<(), Error> {
+        // Require compliance officer role
+        roles::require_role(&env, Role::ComplianceOfficer, &caller)?;
+
+        compliance::write_allowlisted(&env, &account, allowed);
+        extend_instance(&env);
 
         // Emit event
-        env.events().publish(
-            (symbol_short!("burn_frm"), spender, from),
-            amount,
-        );
+        AllowlistUpdatedEvent { account, allowed }.publish(&env);
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    /// Check whether transfers to an account are allowed under the
+    /// `AllowlistOnly` policy
+    pub fn is_allowlisted(env: Env, account: Address) -> bool {
+        compliance::is_allowlisted(&env, &account)
+    }
 
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let contract_id = env.register(TurToken {}, ());
-        let client = TurTokenClient::new(&env, &contract_id);
+    /// Set the account receiving clawed back tokens (admin role)
+    pub fn set_treasury(env: Env, caller: Address, treasury: Address) -> Result<(), Error> {
+        // Require admin role
+        roles::require_role(&env, Role::Admin, &caller)?;
 
-        let admin = Address::generate(&env);
-        let name = String::from_str(&env, "Turistas Token");
-        let symbol = String::from_str(&env, "TUR");
-        let decimals = 7;
-        let initial_supply = 10_000_000_0000000; // 10M TUR with 7 decimals
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        extend_instance(&env);
 
-        client.initialize(&admin, &name, &symbol, &decimals, &initial_supply);
+        // Emit event
+        TreasuryUpdatedEvent { treasury }.publish(&env);
 
-        assert_eq!(client.name(), name);
-        assert_eq!(client.symbol(), symbol);
-        assert_eq!(client.decimals(), decimals);
+        Ok(())
+    }
+
+    /// Get the account receiving clawed back tokens
+    pub fn get_treasury(env: Env) -> Address {
+        compliance::read_treasury(&env)
+    }
+
+    /// Make points minted from now on expire after `lifetime_seconds`, or
+    /// stop expiring new points with 0 (admin role). Existing lots keep
+    /// their expiry.
+    pub fn set_points_lifetime(
+        env: Env,
+        caller: Address,
+        lifetime_seconds: u64,
+    ) -> Result<(), Error> {
+        // Require admin role
+        roles::require_role(&env, Role::Admin, &caller)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PointsLifetime, &lifetime_seconds);
+        extend_instance(&env);
+
+        // Emit event
+        PointsLifetimeUpdatedEvent { lifetime_seconds }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get how long minted points stay usable in seconds, 0 when they
+    /// never expire
+    pub fn points_lifetime(env: Env) -> u64 {
+        lots::read_lifetime(&env)
+    }
+
+    /// Stop or resume tracking expiry for an account (admin role). Meant
+    /// for pools such as the staking contract, which hold points for many
+    /// users: points do not expire while in the pool, but keep their
+    /// expiry dates per depositor and expire once returned. Exempting an
+    /// account drops its own lots, including expired ones that were not
+    /// burned yet.
+    pub fn set_expiry_exempt(
+        env: Env,
+        caller: Address,
+        account: Address,
+        exempt: bool,
+    ) -> Result<(), Error> {
+        // Require admin role
+        roles::require_role(&env, Role::Admin, &caller)?;
+
+        lots::write_exempt(&env, &account, exempt);
+        extend_instance(&env);
+
+        // Emit event
+        ExpiryExemptionUpdatedEvent { account, exempt }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Check whether points do not expire while held by an account
+    pub fn is_expiry_exempt(env: Env, account: Address) -> bool {
+        lots::is_exempt(&env, &account)
+    }
+
+    /// Get the dated lots of an account, soonest to expire first
+    pub fn get_lots(env: Env, id: Address) -> Vec<Lot> {
+        lots::read_lots(&env, &id)
+    }
+
+    /// Get the lots an exempt account holds for the points `depositor`
+    /// sent it, soonest to expire first
+    pub fn get_held_lots(env: Env, holder: Address, depositor: Address) -> Vec<Lot> {
+        lots::read_held_lots(&env, &holder, &depositor)
+    }
+
+    /// Burn the expired points of an account and return the amount burned;
+    /// anyone can sweep any account
+    pub fn expire(env: Env, id: Address) -> i128 {
+        let amount = Self::burn_expired(&env, &id);
+        if amount > 0 {
+            extend_instance(&env);
+        }
+        amount
+    }
+
+    fn set_frozen(env: &Env, account: Address, frozen: bool) {
+        compliance::write_frozen(env, &account, frozen);
+        extend_instance(env);
+
+        // Emit event
+        AccountFrozenEvent { account, frozen }.publish(env);
+    }
+
+    fn update_role(env: &Env, role: Role, account: Address, granted: bool) {
+        roles::write_role(env, role, &account, granted);
+        extend_instance(env);
+
+        // Emit event
+        RoleUpdatedEvent {
+            role: role as u32,
+            account,
+            granted,
+        }
+        .publish(env);
+    }
+
+    fn set_paused(env: &Env, caller: Address, paused: bool) {
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        extend_instance(env);
+
+        // Emit event
+        PausedEvent { caller, paused }.publish(env);
+    }
+
+    fn check_not_paused(env: &Env) {
+        if Self::is_paused(env.clone()) {
+            panic_with_error!(env, Error::Paused);
+        }
+    }
+
+    fn check_nonnegative_amount(env: &Env, amount: i128) {
+        if amount < 0 {
+            panic_with_error!(env, Error::NegativeAmount);
+        }
+    }
+
+    /// Burn the points held in expired lots of an account. Balances are
+    /// swept before their lots change, so expired lots never pile up.
+    fn burn_expired(env: &Env, id: &Address) -> i128 {
+        let amount = lots::sweep(env, id);
+        if amount == 0 {
+            return 0;
+        }
+
+        let balance = balance::read_balance(env, id) - amount;
+        balance::write_balance(env, id, balance);
+        Self::burn_supply(env, amount);
+
+        // Emit events; the standard burn keeps balance indexers in sync
+        TokenUtils::new(env).events().burn(id.clone(), amount);
+        PointsExpiredEvent {
+            account: id.clone(),
+            amount,
+        }
+        .publish(env);
+
+        amount
+    }
+
+    fn burn_supply(env: &Env, amount: i128) {
+        let total_supply = Self::total_supply(env.clone()) - amount;
+        Self::write_total_supply(env, total_supply);
+    }
+
+    fn write_total_supply(env: &Env, total_supply: i128) {
+        let previous = Self::total_supply(env.clone());
+        checkpoints::record(env, &checkpoints::Series::Supply, previous, total_supply);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply, &total_supply);
+    }
+}
+
+// Keep the contract instance alive while the token is in use
+fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Standard token interface (SEP-41), with the standard event format
+#[contractimpl]
+impl token::TokenInterface for TurToken {
+    /// Get allowance amount, zero once expired
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        allowance::read_allowance(&env, &from, &spender).amount
+    }
+
+    /// Allow a spender to transfer or burn tokens of the owner until `expiration_ledger`
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        // Require owner authentication
+        from.require_auth();
+
+        Self::check_nonnegative_amount(&env, amount);
+        allowance::write_allowance(&env, &from, &spender, amount, expiration_ledger);
+
+        extend_instance(&env);
+
+        // Emit event
+        TokenUtils::new(&env)
+            .events()
+            .approve(from, spender, amount, expiration_ledger);
+    }
+
+    /// Get balance of an address, without expired points
+    fn balance(env: Env, id: Address) -> i128 {
+        balance::spendable_balance(&env, &id)
+    }
+
+    /// Transfer tokens from one address to another
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        // Require authentication
+        from.require_auth();
+
+        Self::check_not_paused(&env);
+        compliance::check_not_frozen(&env, &from);
+        compliance::check_transfer_allowed(&env, &to);
+        Self::check_nonnegative_amount(&env, amount);
+
+        // Update balances. The recipient is credited after the debit so
+        // that a transfer to oneself leaves the balance unchanged.
+        let lots = balance::spend_balance(&env, &from, amount, Some(&to));
+        balance::receive_balance(&env, &to, &from, amount, &lots);
+
+        extend_instance(&env);
+
+        // Emit event
+        TokenUtils::new(&env).events().transfer(from, to, amount);
+    }
+
+    /// Transfer tokens of `from` using the spender's allowance
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        // Require spender authentication
+        spender.require_auth();
+
+        Self::check_not_paused(&env);
+        compliance::check_not_frozen(&env, &from);
+        compliance::check_not_frozen(&env, &spender);
+        compliance::check_transfer_allowed(&env, &to);
+        Self::check_nonnegative_amount(&env, amount);
+        allowance::spend_allowance(&env, &from, &spender, amount);
+        let lots = balance::spend_balance(&env, &from, amount, Some(&to));
+        balance::receive_balance(&env, &to, &from, amount, &lots);
+
+        extend_instance(&env);
+
+        // Emit event
+        TokenUtils::new(&env).events().transfer(from, to, amount);
+    }
+
+    /// Burn tokens from an address
+    fn burn(env: Env, from: Address, amount: i128) {
+        // Require authentication
+        from.require_auth();
+
+        Self::check_not_paused(&env);
+        compliance::check_not_frozen(&env, &from);
+        Self::check_nonnegative_amount(&env, amount);
+        balance::spend_balance(&env, &from, amount, None);
+        Self::burn_supply(&env, amount);
+
+        extend_instance(&env);
+
+        // Emit event
+        TokenUtils::new(&env).events().burn(from, amount);
+    }
+
+    /// Burn tokens from an address using allowance
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        // Require spender authentication
+        spender.require_auth();
+
+        Self::check_not_paused(&env);
+        compliance::check_not_frozen(&env, &from);
+        compliance::check_not_frozen(&env, &spender);
+        Self::check_nonnegative_amount(&env, amount);
+        allowance::spend_allowance(&env, &from, &spender, amount);
+        balance::spend_balance(&env, &from, amount, None);
+        Self::burn_supply(&env, amount);
+
+        extend_instance(&env);
+
+        // Emit event
+        TokenUtils::new(&env).events().burn(from, amount);
+    }
+
+    /// Get token decimals
+    fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or(7)
+    }
+
+    /// Get token name
+    fn name(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Name)
+            .unwrap_or(String::from_str(&env, "Turistas Token"))
+    }
+
+    /// Get token symbol
+    fn symbol(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Symbol)
+            .unwrap_or(String::from_str(&env, "TUR"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::{collection::vec as vec_of, prelude::*};
+    use soroban_sdk::{
+        symbol_short,
+        testutils::{
+            storage::{Instance as _, Persistent as _, Temporary as _},
+            Address as _, EnvTestConfig, Events, Ledger,
+        },
+        vec, Env, IntoVal, InvokeError, Symbol,
+    };
+    use testutils::{Harness, Op};
+
+    // 100M TUR with 7 decimals
+    const MAX_SUPPLY: i128 = 100_000_000_0000000;
+
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let name = String::from_str(&env, "Turistas Token");
+        let symbol = String::from_str(&env, "TUR");
+        let decimals = 7;
+        let initial_supply = 10_000_000_0000000; // 10M TUR with 7 decimals
+
+        client.initialize(&admin, &name, &symbol, &decimals, &initial_supply, &MAX_SUPPLY);
+
+        assert_eq!(client.name(), name);
+        assert_eq!(client.symbol(), symbol);
+        assert_eq!(client.decimals(), decimals);
         assert_eq!(client.total_supply(), initial_supply);
         assert_eq!(client.balance(&admin), initial_supply);
+        assert_eq!(client.max_supply(), MAX_SUPPLY);
+        assert_eq!(client.remaining_mintable(), MAX_SUPPLY - initial_supply);
+
+        // Initialization cannot be repeated to replace the admin or the cap
+        let attacker = Address::generate(&env);
+        let result = client.try_initialize(
+            &attacker,
+            &name,
+            &symbol,
+            &decimals,
+            &0,
+            &(MAX_SUPPLY * 2),
+        );
+        assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+        assert_eq!(client.max_supply(), MAX_SUPPLY);
+        assert!(client.has_role(&Role::Admin, &admin));
+        assert!(!client.has_role(&Role::Admin, &attacker));
+    }
+
+    #[test]
+    fn test_supply_cap() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let minter = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Turistas Token");
+        let symbol = String::from_str(&env, "TUR");
+
+        // The initial supply must fit under the cap
+        assert_eq!(
+            client.try_initialize(&admin, &name, &symbol, &7, &1_001, &1_000),
+            Err(Ok(Error::SupplyCapExceeded))
+        );
+        client.initialize(&admin, &name, &symbol, &7, &600, &1_000);
+        client.set_authorized_minter(&admin, &minter, &true);
+        client.set_minter_limits(
+            &admin,
+            &minter,
+            &MinterLimits {
+                quota: 1_000,
+                rate_limit: 1_000,
+                period_seconds: 3_600,
+            },
+        );
+
+        // Every mint path stops at the cap
+        client.mint(&admin, &user, &300);
+        assert_eq!(client.remaining_mintable(), 100);
+        assert_eq!(client.try_mint(&admin, &user, &101), Err(Ok(Error::SupplyCapExceeded)));
+        assert_eq!(
+            client.try_mint_by_authorized(&minter, &user, &101),
+            Err(Ok(Error::SupplyCapExceeded))
+        );
+        client.mint_by_authorized(&minter, &user, &100);
+        assert_eq!(client.total_supply(), 1_000);
+        assert_eq!(client.remaining_mintable(), 0);
+
+        // Burned tokens free room under the cap
+        client.burn(&user, &50);
+        assert_eq!(client.remaining_mintable(), 50);
+        client.mint(&admin, &user, &50);
+        assert_eq!(client.total_supply(), client.max_supply());
     }
 
     #[test]
@@ -449,6 +1108,7 @@ mod test {
             &String::from_str(&env, "TUR"),
             &7,
             &initial_supply,
+            &MAX_SUPPLY,
         );
 
         let transfer_amount = 1000_0000000;
@@ -475,6 +1135,7 @@ mod test {
             &String::from_str(&env, "TUR"),
             &7,
             &initial_supply,
+            &MAX_SUPPLY,
         );
 
         let burn_amount = 1000_0000000;
@@ -483,4 +1144,1112 @@ mod test {
         assert_eq!(client.balance(&admin), initial_supply - burn_amount);
         assert_eq!(client.total_supply(), initial_supply - burn_amount);
     }
+
+    #[test]
+    fn test_transfer_from_spends_allowance() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let user = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &10_000_0000000,
+            &MAX_SUPPLY,
+        );
+
+        let expiration_ledger = env.ledger().sequence() + 100;
+        client.approve(&admin, &spender, &3000_0000000, &expiration_ledger);
+        client.transfer_from(&spender, &admin, &user, &1000_0000000);
+
+        assert_eq!(client.balance(&user), 1000_0000000);
+        assert_eq!(client.allowance(&admin, &spender), 2000_0000000);
+        assert_eq!(
+            client.try_transfer_from(&spender, &admin, &user, &2000_0000001),
+            Err(Ok(Error::InsufficientAllowance.into()))
+        );
+
+        // Allowances read as zero once their expiration ledger has passed
+        env.ledger().with_mut(|ledger| ledger.sequence_number = expiration_ledger + 1);
+        assert_eq!(client.allowance(&admin, &spender), 0);
+        assert_eq!(
+            client.try_burn_from(&spender, &admin, &1),
+            Err(Ok(Error::InsufficientAllowance.into()))
+        );
+        assert_eq!(
+            client.try_approve(&admin, &spender, &1, &expiration_ledger),
+            Err(Ok(Error::InvalidExpirationLedger.into()))
+        );
+    }
+
+    #[test]
+    fn test_standard_events() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &10_000_0000000,
+            &MAX_SUPPLY,
+        );
+
+        client.transfer(&admin, &user, &1000_0000000);
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (symbol_short!("transfer"), admin.clone(), user.clone()).into_val(&env),
+                    1000_0000000i128.into_val(&env),
+                ),
+            ]
+        );
+
+        client.approve(&user, &admin, &500_0000000, &200);
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (Symbol::new(&env, "approve"), user.clone(), admin.clone()).into_val(&env),
+                    (500_0000000i128, 200u32).into_val(&env),
+                ),
+            ]
+        );
+
+        client.burn(&user, &100_0000000);
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    contract_id,
+                    (symbol_short!("burn"), user).into_val(&env),
+                    100_0000000i128.into_val(&env),
+                ),
+            ]
+        );
+    }
+
+    fn amount() -> impl Strategy<Value = i128> {
+        prop_oneof![
+            8 => 0..=testutils::INITIAL_SUPPLY,
+            1 => -1_000i128..0,
+            1 => Just(i128::MAX),
+        ]
+    }
+
+    fn account() -> impl Strategy<Value = u8> {
+        0..testutils::ACCOUNTS as u8
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (account(), amount()).prop_map(|(to, amount)| Op::Mint { to, amount }),
+            (account(), account(), amount())
+                .prop_map(|(from, to, amount)| Op::Transfer { from, to, amount }),
+            (account(), amount()).prop_map(|(from, amount)| Op::Burn { from, amount }),
+            (account(), account(), amount()).prop_map(|(owner, spender, amount)| {
+                Op::Approve {
+                    owner,
+                    spender,
+                    amount,
+                }
+            }),
+            (account(), account(), account(), amount()).prop_map(
+                |(spender, from, to, amount)| Op::TransferFrom {
+                    spender,
+                    from,
+                    to,
+                    amount,
+                }
+            ),
+            (account(), account(), amount()).prop_map(|(spender, from, amount)| {
+                Op::BurnFrom {
+                    spender,
+                    from,
+                    amount,
+                }
+            }),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_invariants_hold_for_random_operations(ops in vec_of(op(), 1..40)) {
+            let mut harness = Harness::new();
+            for op in &ops {
+                harness.apply(op);
+            }
+        }
+    }
+
+    #[test]
+    fn test_transfer_to_self_keeps_balance() {
+        let mut harness = Harness::new();
+        harness.apply(&Op::Transfer {
+            from: 0,
+            to: 0,
+            amount: 1_000,
+        });
+    }
+
+    #[test]
+    fn test_balances_and_allowances_leave_instance_storage() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let spender = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &10_000_0000000,
+            &MAX_SUPPLY,
+        );
+
+        let expiration_ledger = env.ledger().sequence() + 100;
+        client.approve(&admin, &spender, &1000_0000000, &expiration_ledger);
+
+        env.as_contract(&contract_id, || {
+            let balance_key = DataKey::Balance(admin.clone());
+            let allowance_key = DataKey::Allowance(admin.clone(), spender.clone());
+
+            assert!(!env.storage().instance().has(&balance_key));
+            assert!(!env.storage().instance().has(&allowance_key));
+            assert_eq!(env.storage().persistent().get_ttl(&balance_key), BALANCE_BUMP_AMOUNT);
+            assert_eq!(
+                env.storage().temporary().get_ttl(&allowance_key),
+                expiration_ledger - env.ledger().sequence()
+            );
+            assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+        });
+    }
+
+    // Balance handling of the token before balances moved to persistent
+    // storage, standing in for the code of an already deployed token
+    mod legacy {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        use crate::DataKey;
+
+        #[contract]
+        pub struct LegacyTurToken;
+
+        #[contractimpl]
+        impl LegacyTurToken {
+            pub fn mint(env: Env, to: Address, amount: i128) {
+                let key = DataKey::Balance(to);
+                let balance: i128 = env.storage().instance().get(&key).unwrap_or(0);
+                env.storage().instance().set(&key, &(balance + amount));
+            }
+        }
+    }
+
+    #[test]
+    fn test_migrate_balances_from_instance_storage() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let holder = Address::generate(&env);
+        let other = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &10_000_0000000,
+            &MAX_SUPPLY,
+        );
+
+        // Balances minted while the token ran the legacy code, which kept
+        // them in the instance
+        env.register_at(&contract_id, legacy::LegacyTurToken, ());
+        let legacy_client = legacy::LegacyTurTokenClient::new(&env, &contract_id);
+        legacy_client.mint(&holder, &500_0000000);
+        legacy_client.mint(&other, &200_0000000);
+
+        // Upgrading to the current code keeps the instance storage
+        env.register_at(&contract_id, TurToken {}, ());
+        env.as_contract(&contract_id, || {
+            for id in [&holder, &other] {
+                let key = DataKey::Balance(id.clone());
+                assert!(env.storage().instance().has(&key));
+                assert!(!env.storage().persistent().has(&key));
+            }
+        });
+
+        // Legacy balances are still readable and spendable before migrating
+        assert_eq!(client.balance(&holder), 500_0000000);
+        client.transfer(&other, &admin, &50_0000000);
+        assert_eq!(client.balance(&other), 150_0000000);
+
+        // Only the admin can migrate
+        let stranger = Address::generate(&env);
+        assert_eq!(
+            client.try_migrate_balances(&stranger, &vec![&env, holder.clone()]),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        // Only the holder still has an instance entry to move
+        assert_eq!(client.migrate_balances(&admin, &vec![&env, holder.clone(), other.clone()]), 1);
+        assert_eq!(client.balance(&holder), 500_0000000);
+        assert_eq!(client.balance(&other), 150_0000000);
+        assert_eq!(client.total_supply(), 10_000_0000000);
+
+        env.as_contract(&contract_id, || {
+            for id in [&holder, &other] {
+                let key = DataKey::Balance(id.clone());
+                assert!(!env.storage().instance().has(&key));
+                assert!(env.storage().persistent().has(&key));
+            }
+        });
+
+        // Migrating again finds nothing left to move
+        assert_eq!(client.migrate_balances(&admin, &vec![&env, holder.clone()]), 0);
+    }
+
+    #[test]
+    fn test_upgrade_requires_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &10_000_0000000,
+            &MAX_SUPPLY,
+        );
+
+        let new_wasm_hash = BytesN::from_array(&env, &[1; 32]);
+        assert_eq!(
+            client.try_upgrade(&stranger, &new_wasm_hash),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        // The admin gets past the role check, and the host rejects a hash
+        // that was never uploaded
+        assert_eq!(
+            client.try_upgrade(&admin, &new_wasm_hash),
+            Err(Err(InvokeError::Abort))
+        );
+    }
+
+    #[test]
+    fn test_minter_quota_and_rate_limit() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let minter = Address::generate(&env);
+        let user = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &0,
+            &MAX_SUPPLY,
+        );
+
+        // Authorized minters cannot mint until their limits are set
+        client.set_authorized_minter(&admin, &minter, &true);
+        assert_eq!(client.remaining_minter_allowance(&minter), 0);
+        assert_eq!(
+            client.try_mint_by_authorized(&minter, &user, &1),
+            Err(Ok(Error::MinterQuotaExceeded))
+        );
+
+        assert_eq!(
+            client.try_set_minter_limits(
+                &admin,
+                &minter,
+                &MinterLimits {
+                    quota: 1_000,
+                    rate_limit: 300,
+                    period_seconds: 0,
+                },
+            ),
+            Err(Ok(Error::InvalidMinterLimits))
+        );
+        let limits = MinterLimits {
+            quota: 1_000,
+            rate_limit: 300,
+            period_seconds: 3_600,
+        };
+        client.set_minter_limits(&admin, &minter, &limits);
+        assert_eq!(client.get_minter_limits(&minter), Some(limits.clone()));
+
+        // The rate limit caps each window
+        client.mint_by_authorized(&minter, &user, &200);
+        assert_eq!(client.remaining_minter_allowance(&minter), 100);
+        assert_eq!(
+            client.try_mint_by_authorized(&minter, &user, &101),
+            Err(Ok(Error::MinterRateLimited))
+        );
+        client.mint_by_authorized(&minter, &user, &100);
+
+        // A new window starts once the period has passed
+        env.ledger().with_mut(|ledger| ledger.timestamp += 3_600);
+        assert_eq!(client.remaining_minter_allowance(&minter), 300);
+        client.mint_by_authorized(&minter, &user, &300);
+        env.ledger().with_mut(|ledger| ledger.timestamp += 3_600);
+        client.mint_by_authorized(&minter, &user, &300);
+
+        // The quota caps the total across windows
+        env.ledger().with_mut(|ledger| ledger.timestamp += 3_600);
+        assert_eq!(client.remaining_minter_allowance(&minter), 100);
+        assert_eq!(
+            client.try_mint_by_authorized(&minter, &user, &101),
+            Err(Ok(Error::MinterQuotaExceeded))
+        );
+        client.mint_by_authorized(&minter, &user, &100);
+        assert_eq!(client.get_minter_usage(&minter).total_minted, 1_000);
+        assert_eq!(client.balance(&user), 1_000);
+
+        // Raising the quota lets the minter go on; revoking stops it
+        client.set_minter_limits(
+            &admin,
+            &minter,
+            &MinterLimits {
+                quota: 2_000,
+                ..limits
+            },
+        );
+        assert_eq!(client.remaining_minter_allowance(&minter), 200);
+        client.set_authorized_minter(&admin, &minter, &false);
+        assert_eq!(client.remaining_minter_allowance(&minter), 0);
+        assert_eq!(
+            client.try_mint_by_authorized(&minter, &user, &1),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    // Contract minting TUR rewards through `mint_by_authorized`
+    #[contract]
+    struct RewardMinter;
+
+    #[contractimpl]
+    impl RewardMinter {
+        pub fn reward(env: Env, token: Address, to: Address, amount: i128) {
+            TurTokenClient::new(&env, &token).mint_by_authorized(
+                &env.current_contract_address(),
+                &to,
+                &amount,
+            );
+        }
+    }
+
+    #[test]
+    fn test_only_the_minter_can_mint_by_authorized() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+        let minter_id = env.register(RewardMinter, ());
+        let minter = RewardMinterClient::new(&env, &minter_id);
+
+        let admin = Address::generate(&env);
+        let traveler = Address::generate(&env);
+        let attacker = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &0,
+            &MAX_SUPPLY,
+        );
+        client.set_authorized_minter(&admin, &minter_id, &true);
+        client.set_minter_limits(
+            &admin,
+            &minter_id,
+            &MinterLimits {
+                quota: 10_000,
+                rate_limit: 10_000,
+                period_seconds: 86_400,
+            },
+        );
+
+        // No authorization is mocked from here on
+        env.set_auths(&[]);
+
+        // The minter contract authorizes its own calls
+        minter.reward(&contract_id, &traveler, &1_000);
+        assert_eq!(client.balance(&traveler), 1_000);
+
+        // Anyone else passing the minter's address is rejected
+        assert!(client
+            .try_mint_by_authorized(&minter_id, &attacker, &1_000)
+            .is_err());
+        assert_eq!(client.balance(&attacker), 0);
+        assert_eq!(client.total_supply(), 1_000);
+        assert_eq!(client.get_minter_usage(&minter_id).total_minted, 1_000);
+    }
+
+    #[test]
+    fn test_roles_delegate_narrow_powers() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let manager = Address::generate(&env);
+        let pauser = Address::generate(&env);
+        let minter = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+
+        // Only admins grant roles
+        assert_eq!(
+            client.try_grant_role(&manager, &Role::MinterManager, &manager),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.grant_role(&admin, &Role::MinterManager, &manager);
+        client.grant_role(&admin, &Role::Pauser, &pauser);
+        assert!(client.has_role(&Role::MinterManager, &manager));
+        assert!(!client.has_role(&Role::Pauser, &manager));
+        assert!(client.has_role(&Role::ComplianceOfficer, &admin));
+
+        // Each role is limited to its own entrypoints
+        client.set_authorized_minter(&manager, &minter, &true);
+        assert_eq!(
+            client.try_set_authorized_minter(&pauser, &minter, &false),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(client.try_mint(&manager, &manager, &1), Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.try_pause(&manager), Err(Ok(Error::Unauthorized)));
+        assert_eq!(
+            client.try_grant_role(&manager, &Role::Pauser, &manager),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        // Roles can be revoked by an admin or renounced by their holder
+        client.revoke_role(&admin, &Role::MinterManager, &manager);
+        assert!(!client.has_role(&Role::MinterManager, &manager));
+        client.renounce_role(&pauser, &Role::Pauser);
+        assert_eq!(client.try_pause(&pauser), Err(Ok(Error::Unauthorized)));
+
+        // except for the initial admin, which always holds every role
+        assert_eq!(
+            client.try_revoke_role(&admin, &Role::Pauser, &admin),
+            Err(Ok(Error::InitialAdminRole))
+        );
+        assert_eq!(
+            client.try_renounce_role(&admin, &Role::Admin),
+            Err(Ok(Error::InitialAdminRole))
+        );
+        assert!(client.has_role(&Role::Admin, &admin));
+
+        // A delegated admin holds every role
+        client.grant_role(&admin, &Role::Admin, &manager);
+        client.pause(&manager);
+        assert!(client.is_paused());
+    }
+
+    #[test]
+    fn test_pause_stops_balance_changes() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let pauser = Address::generate(&env);
+        let user = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+        client.grant_role(&admin, &Role::Pauser, &pauser);
+        client.approve(&admin, &user, &500, &(env.ledger().sequence() + 100));
+
+        client.pause(&pauser);
+        assert_eq!(
+            client.try_transfer(&admin, &user, &100),
+            Err(Ok(Error::Paused.into()))
+        );
+        assert_eq!(
+            client.try_transfer_from(&user, &admin, &user, &100),
+            Err(Ok(Error::Paused.into()))
+        );
+        assert_eq!(client.try_burn(&admin, &100), Err(Ok(Error::Paused.into())));
+        assert_eq!(
+            client.try_burn_from(&user, &admin, &100),
+            Err(Ok(Error::Paused.into()))
+        );
+        assert_eq!(client.try_mint(&admin, &user, &100), Err(Ok(Error::Paused)));
+
+        client.unpause(&pauser);
+        client.transfer(&admin, &user, &100);
+        assert_eq!(client.balance(&user), 100);
+    }
+
+    #[test]
+    fn test_freeze_and_clawback() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let officer = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let thief = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+        client.grant_role(&admin, &Role::ComplianceOfficer, &officer);
+        client.set_treasury(&admin, &treasury);
+        client.transfer(&admin, &thief, &300);
+        client.approve(&thief, &merchant, &300, &(env.ledger().sequence() + 100));
+
+        // Only compliance officers freeze accounts
+        assert_eq!(client.try_freeze(&merchant, &thief), Err(Ok(Error::Unauthorized)));
+        client.freeze(&officer, &thief);
+        assert!(client.is_frozen(&thief));
+
+        // A frozen account can neither spend nor be spent from
+        let frozen = Err(Ok(Error::AccountFrozen.into()));
+        assert_eq!(client.try_transfer(&thief, &merchant, &100), frozen);
+        assert_eq!(client.try_burn(&thief, &100), frozen);
+        assert_eq!(client.try_transfer_from(&merchant, &thief, &merchant, &100), frozen);
+        assert_eq!(client.try_burn_from(&merchant, &thief, &100), frozen);
+
+        // It can still receive tokens
+        client.transfer(&admin, &thief, &100);
+
+        // Clawed back tokens go to the treasury
+        assert_eq!(
+            client.try_clawback(&officer, &thief, &401),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        client.clawback(&officer, &thief, &400);
+        assert_eq!(
+            env.events().all().slice(0..1),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (symbol_short!("transfer"), thief.clone(), treasury.clone()).into_val(&env),
+                    400i128.into_val(&env),
+                ),
+            ]
+        );
+        assert_eq!(client.balance(&thief), 0);
+        assert_eq!(client.balance(&treasury), 400);
+        assert_eq!(client.total_supply(), 1_000);
+
+        client.unfreeze(&officer, &thief);
+        assert!(!client.is_frozen(&thief));
+        client.transfer(&treasury, &thief, &50);
+        client.transfer(&thief, &merchant, &50);
+        assert_eq!(client.balance(&merchant), 50);
+    }
+
+    #[test]
+    fn test_expiring_points() {
+        const DAY: u64 = 86_400;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+
+        // Points minted before expiry is on never expire
+        assert_eq!(client.points_lifetime(), 0);
+        assert_eq!(client.get_lots(&admin), Vec::new(&env));
+        assert_eq!(
+            client.try_set_points_lifetime(&alice, &(10 * DAY)),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.set_points_lifetime(&admin, &(10 * DAY));
+
+        // Each mint creates a lot expiring at the end of a day
+        client.mint(&admin, &alice, &100);
+        env.ledger().set_timestamp(1_000 + 2 * DAY);
+        client.mint(&admin, &alice, &200);
+        let lot = |amount: i128, expires_at: u64| Lot { amount, expires_at };
+        assert_eq!(
+            client.get_lots(&alice),
+            vec![&env, lot(100, 11 * DAY), lot(200, 13 * DAY)]
+        );
+
+        // Spending takes the oldest lots first, and the points keep their
+        // expiry in the hands of the receiver
+        client.transfer(&alice, &bob, &150);
+        client.transfer(&admin, &alice, &50);
+        assert_eq!(client.get_lots(&alice), vec![&env, lot(150, 13 * DAY)]);
+        assert_eq!(
+            client.get_lots(&bob),
+            vec![&env, lot(100, 11 * DAY), lot(50, 13 * DAY)]
+        );
+
+        // Expired points no longer count in the balance
+        env.ledger().set_timestamp(11 * DAY);
+        assert_eq!(client.balance(&bob), 50);
+        assert_eq!(
+            client.try_transfer(&bob, &alice, &60),
+            Err(Ok(Error::InsufficientBalance.into()))
+        );
+        assert_eq!(client.total_supply(), 1_300);
+
+        // Sweeping burns them
+        assert_eq!(client.expire(&bob), 100);
+        assert_eq!(
+            env.events().all().slice(0..1),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (symbol_short!("burn"), bob.clone()).into_val(&env),
+                    100i128.into_val(&env),
+                ),
+            ]
+        );
+        assert_eq!(client.total_supply(), 1_200);
+        assert_eq!(client.balance(&bob), 50);
+        assert_eq!(client.get_lots(&bob), vec![&env, lot(50, 13 * DAY)]);
+        assert_eq!(client.expire(&bob), 0);
+
+        // Points without a lot stay usable. Both sides of a transfer burn
+        // their expired points first, so expired lots do not pile up.
+        env.ledger().set_timestamp(13 * DAY);
+        assert_eq!(client.balance(&alice), 50);
+        client.transfer(&alice, &bob, &50);
+        assert_eq!(client.expire(&alice), 0);
+        assert_eq!(client.balance(&alice), 0);
+        assert_eq!(client.get_lots(&alice), Vec::new(&env));
+        assert_eq!(client.balance(&bob), 50);
+        assert_eq!(client.get_lots(&bob), Vec::new(&env));
+        assert_eq!(client.total_supply(), 1_000);
+
+        // Minting sweeps the account too
+        client.mint(&admin, &bob, &10);
+        env.ledger().set_timestamp(23 * DAY);
+        client.mint(&admin, &bob, &20);
+        assert_eq!(client.get_lots(&bob), vec![&env, lot(20, 33 * DAY)]);
+        assert_eq!(client.balance(&bob), 70);
+        assert_eq!(client.total_supply(), 1_020);
+    }
+
+    #[test]
+    fn test_transfer_policy() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let officer = Address::generate(&env);
+        let traveler = Address::generate(&env);
+        let friend = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+        client.grant_role(&admin, &Role::ComplianceOfficer, &officer);
+        client.transfer(&admin, &traveler, &500);
+        client.approve(&traveler, &merchant, &500, &(env.ledger().sequence() + 100));
+        assert_eq!(client.transfer_policy(), TransferPolicy::Transferable);
+
+        // Only compliance officers restrict transfers
+        assert_eq!(
+            client.try_set_transfer_policy(&traveler, &TransferPolicy::Locked),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            client.try_set_allowlisted(&traveler, &merchant, &true),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        // Only allowlisted recipients, through either path
+        client.set_transfer_policy(&officer, &TransferPolicy::AllowlistOnly);
+        client.set_allowlisted(&officer, &merchant, &true);
+        assert!(client.is_allowlisted(&merchant));
+        let not_allowlisted = Err(Ok(Error::RecipientNotAllowlisted.into()));
+        assert_eq!(client.try_transfer(&traveler, &friend, &100), not_allowlisted);
+        assert_eq!(
+            client.try_transfer_from(&merchant, &traveler, &friend, &100),
+            not_allowlisted
+        );
+        client.transfer(&traveler, &merchant, &100);
+        client.transfer_from(&merchant, &traveler, &merchant, &100);
+        assert_eq!(client.balance(&merchant), 200);
+
+        // Locked stops every transfer, but points can still be minted and
+        // burned
+        client.set_transfer_policy(&officer, &TransferPolicy::Locked);
+        let locked = Err(Ok(Error::TransfersLocked.into()));
+        assert_eq!(client.try_transfer(&traveler, &merchant, &100), locked);
+        assert_eq!(
+            client.try_transfer_from(&merchant, &traveler, &merchant, &100),
+            locked
+        );
+        client.mint(&admin, &traveler, &100);
+        client.burn_from(&merchant, &traveler, &100);
+        client.burn(&traveler, &100);
+        assert_eq!(client.balance(&traveler), 200);
+
+        client.set_allowlisted(&officer, &merchant, &false);
+        assert!(!client.is_allowlisted(&merchant));
+        client.set_transfer_policy(&officer, &TransferPolicy::Transferable);
+        client.transfer(&traveler, &friend, &100);
+        assert_eq!(client.balance(&friend), 100);
+    }
+
+    #[test]
+    fn test_balance_checkpoints() {
+        // Hundreds of transfers; too many for a readable snapshot
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        env.ledger().set_sequence_number(10);
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let legacy = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&DataKey::Balance(legacy.clone()), &40i128);
+        });
+
+        // Several changes in one ledger keep the value at its end
+        env.ledger().set_sequence_number(11);
+        client.transfer(&admin, &alice, &300);
+        client.transfer(&alice, &bob, &100);
+        env.ledger().set_sequence_number(20);
+        client.mint(&admin, &bob, &500);
+        env.ledger().set_sequence_number(25);
+        client.burn(&bob, &50);
+        client.transfer(&legacy, &bob, &10);
+        env.ledger().set_sequence_number(30);
+
+        // Only closed ledgers can be queried
+        assert_eq!(client.try_balance_at(&alice, &30), Err(Ok(Error::FutureLedger)));
+        assert_eq!(client.try_total_supply_at(&31), Err(Ok(Error::FutureLedger)));
+
+        for (ledger, admin_balance, alice_balance, bob_balance, total_supply) in [
+            (9, 0, 0, 0, 0),
+            (10, 1_000, 0, 0, 1_000),
+            (11, 700, 200, 100, 1_000),
+            (19, 700, 200, 100, 1_000),
+            (20, 700, 200, 600, 1_500),
+            (29, 700, 200, 560, 1_450),
+        ] {
+            assert_eq!(client.balance_at(&admin, &ledger), admin_balance);
+            assert_eq!(client.balance_at(&alice, &ledger), alice_balance);
+            assert_eq!(client.balance_at(&bob, &ledger), bob_balance);
+            assert_eq!(client.total_supply_at(&ledger), total_supply);
+        }
+
+        // Balances written before checkpoints count as held all along
+        assert_eq!(client.balance_at(&legacy, &1), 40);
+        assert_eq!(client.balance_at(&legacy, &25), 30);
+
+        // Only the most recent checkpoints of an account are kept
+        for ledger in 31..331 {
+            env.ledger().set_sequence_number(ledger);
+            client.transfer(&alice, &bob, &((ledger % 2) as i128));
+            client.transfer(&bob, &alice, &(((ledger + 1) % 2) as i128));
+        }
+        env.ledger().set_sequence_number(331);
+        assert_eq!(client.try_balance_at(&alice, &29), Err(Ok(Error::CheckpointPruned)));
+        assert_eq!(client.balance_at(&alice, &330), client.balance(&alice));
+        assert_eq!(client.balance_at(&alice, &329), client.balance(&alice) - 1);
+        assert_eq!(client.total_supply_at(&29), 1_450);
+    }
+
+    #[test]
+    fn test_vote_delegation() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let guide = Address::generate(&env);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+        client.transfer(&admin, &alice, &300);
+        client.transfer(&admin, &bob, &200);
+
+        // Holders vote with their own balance by default
+        assert_eq!(client.delegates(&alice), None);
+        assert_eq!(client.get_votes(&alice), 300);
+
+        client.delegate(&alice, &guide);
+        assert_eq!(
+            env.events().all().slice(0..1),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (Symbol::new(&env, "delegate"), 1u32).into_val(&env),
+                    DelegateChangedEvent {
+                        delegator: alice.clone(),
+                        from_delegate: None,
+                        to_delegate: guide.clone(),
+                    }
+                    .into_val(&env),
+                ),
+            ]
+        );
+        client.delegate(&bob, &guide);
+        assert_eq!(client.delegates(&alice), Some(guide.clone()));
+        assert_eq!(client.get_votes(&alice), 0);
+        assert_eq!(client.get_votes(&guide), 500);
+
+        // Delegated weight follows transfers, mints and burns
+        client.transfer(&alice, &bob, &100);
+        assert_eq!(client.get_votes(&guide), 500);
+        client.transfer(&admin, &alice, &50);
+        client.mint(&admin, &bob, &100);
+        client.burn(&bob, &50);
+        client.transfer(&admin, &guide, &10);
+        assert_eq!(client.get_votes(&guide), 610);
+
+        // Redelegating moves the whole balance
+        client.delegate(&alice, &bob);
+        assert_eq!(client.get_votes(&guide), 360);
+        assert_eq!(client.get_votes(&bob), 250);
+
+        // Delegating to oneself takes the weight back
+        client.delegate(&alice, &alice);
+        assert_eq!(client.delegates(&alice), None);
+        assert_eq!(client.get_votes(&alice), 250);
+        assert_eq!(client.get_votes(&bob), 0);
+
+        let votes: i128 = [&admin, &alice, &bob, &guide]
+            .into_iter()
+            .map(|account| client.get_votes(account))
+            .sum();
+        assert_eq!(votes, client.total_supply());
+    }
+
+    #[test]
+    fn test_past_votes() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let guide = Address::generate(&env);
+
+        env.ledger().set_sequence_number(10);
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+        client.transfer(&admin, &alice, &300);
+        client.transfer(&admin, &bob, &200);
+
+        env.ledger().set_sequence_number(11);
+        client.delegate(&alice, &guide);
+        client.delegate(&bob, &guide);
+
+        env.ledger().set_sequence_number(12);
+        client.mint(&admin, &bob, &100);
+        client.transfer(&admin, &alice, &50);
+
+        env.ledger().set_sequence_number(13);
+        client.delegate(&alice, &alice);
+
+        env.ledger().set_sequence_number(14);
+        assert_eq!(client.try_get_past_votes(&guide, &14), Err(Ok(Error::FutureLedger)));
+
+        for (ledger, admin_votes, alice_votes, bob_votes, guide_votes) in [
+            (9, 0, 0, 0, 0),
+            (10, 500, 300, 200, 0),
+            (11, 500, 0, 0, 500),
+            (12, 450, 0, 0, 650),
+            (13, 450, 350, 0, 300),
+        ] {
+            assert_eq!(client.get_past_votes(&admin, &ledger), admin_votes);
+            assert_eq!(client.get_past_votes(&alice, &ledger), alice_votes);
+            assert_eq!(client.get_past_votes(&bob, &ledger), bob_votes);
+            assert_eq!(client.get_past_votes(&guide, &ledger), guide_votes);
+        }
+        assert_eq!(client.get_past_votes(&guide, &13), client.get_votes(&guide));
+    }
+
+    #[test]
+    fn test_permit() {
+        use ed25519_dalek::{Signer, SigningKey};
+        use soroban_sdk::{
+            xdr::{AccountId, PublicKey, ScAddress, ScVal, Uint256},
+            InvokeError, TryFromVal,
+        };
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let client = TurTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let account = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+            key.verifying_key().to_bytes(),
+        ))));
+        let tourist = Address::try_from_val(&env, &ScVal::Address(account)).unwrap();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &1_000,
+            &MAX_SUPPLY,
+        );
+        client.transfer(&admin, &tourist, &500);
+
+        let expiration_ledger = env.ledger().sequence() + 100;
+        let sign = |amount: i128, nonce: u64| {
+            let message =
+                client.permit_message(&tourist, &merchant, &amount, &expiration_ledger, &nonce);
+            let mut bytes = [0u8; 1024];
+            let bytes = &mut bytes[..message.len() as usize];
+            message.copy_into_slice(bytes);
+            BytesN::from_array(&env, &key.sign(bytes).to_bytes())
+        };
+
+        // Anyone can submit the permit; the tourist signs nothing else
+        let signature = sign(300, 0);
+        env.set_auths(&[]);
+        client.permit(&tourist, &merchant, &300, &expiration_ledger, &0, &signature);
+        assert_eq!(client.allowance(&tourist, &merchant), 300);
+        assert_eq!(client.permit_nonce(&tourist), 1);
+
+        // A permit cannot be replayed or altered; an invalid signature
+        // aborts the call in the host
+        assert_eq!(
+            client.try_permit(&tourist, &merchant, &300, &expiration_ledger, &0, &signature),
+            Err(Ok(Error::InvalidPermitNonce))
+        );
+        assert_eq!(
+            client.try_permit(&tourist, &merchant, &500, &expiration_ledger, &1, &sign(300, 1)),
+            Err(Err(InvokeError::Abort))
+        );
+        assert_eq!(client.permit_nonce(&tourist), 1);
+
+        // Contract addresses have no key to sign permits
+        assert_eq!(
+            client.try_permit(&contract_id, &merchant, &300, &expiration_ledger, &0, &signature),
+            Err(Ok(Error::InvalidPermitSigner))
+        );
+
+        env.mock_all_auths();
+        client.transfer_from(&merchant, &tourist, &merchant, &300);
+        assert_eq!(client.balance(&merchant), 300);
+        assert_eq!(client.allowance(&tourist, &merchant), 0);
+    }
 }
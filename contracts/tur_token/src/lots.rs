@@ -0,0 +1,218 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::{DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+// Expiry dates are rounded up to the end of a day, so an account holds at
+// most one lot per day of the points lifetime
+const LOT_GRANULARITY_SECONDS: u64 = 86_400;
+
+// Points minted while expiry is on, usable until `expires_at` (exclusive).
+// Lots travel with the points when they are transferred; the part of a
+// balance not covered by lots never expires. Points of an exempt account,
+// such as a staking pool, do not expire there, but it keeps the lots of
+// each depositor aside and hands them back with the points it returns, so
+// a round trip through it does not make points permanent.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lot {
+    pub amount: i128,
+    pub expires_at: u64,
+}
+
+/// Seconds minted points stay usable, 0 when points do not expire
+pub fn read_lifetime(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::PointsLifetime)
+        .unwrap_or(0)
+}
+
+/// Lots of an account, soonest to expire first
+pub fn read_lots(env: &Env, id: &Address) -> Vec<Lot> {
+    read(env, &DataKey::Lots(id.clone()))
+}
+
+fn read(env: &Env, key: &DataKey) -> Vec<Lot> {
+    match env.storage().persistent().get(key) {
+        Some(lots) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+            lots
+        }
+        None => Vec::new(env),
+    }
+}
+
+fn write(env: &Env, key: &DataKey, lots: &Vec<Lot>) {
+    if lots.is_empty() {
+        env.storage().persistent().remove(key);
+        return;
+    }
+
+    env.storage().persistent().set(key, lots);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Lots an exempt account holds on behalf of `depositor`
+pub fn read_held_lots(env: &Env, holder: &Address, depositor: &Address) -> Vec<Lot> {
+    read(env, &DataKey::HeldLots(holder.clone(), depositor.clone()))
+}
+
+// Where the lots of points moving in or out of `id` are kept: with the
+// account, or set aside for the counterparty when the account is exempt.
+// Exempt accounts keep no lots for mints and burns.
+fn lots_key(env: &Env, id: &Address, counterparty: Option<&Address>) -> Option<DataKey> {
+    if !is_exempt(env, id) {
+        return Some(DataKey::Lots(id.clone()));
+    }
+    counterparty.map(|counterparty| DataKey::HeldLots(id.clone(), counterparty.clone()))
+}
+
+pub fn is_exempt(env: &Env, id: &Address) -> bool {
+    let key = DataKey::ExpiryExempt(id.clone());
+    match env.storage().persistent().get(&key) {
+        Some(exempt) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+            exempt
+        }
+        None => false,
+    }
+}
+
+pub fn write_exempt(env: &Env, id: &Address, exempt: bool) {
+    let key = DataKey::ExpiryExempt(id.clone());
+    if !exempt {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    write(env, &DataKey::Lots(id.clone()), &Vec::new(env));
+}
+
+fn is_expired(env: &Env, lot: &Lot) -> bool {
+    lot.expires_at <= env.ledger().timestamp()
+}
+
+/// Amount of the account's balance held in expired lots
+pub fn expired_amount(env: &Env, id: &Address) -> i128 {
+    read_lots(env, id)
+        .iter()
+        .filter(|lot| is_expired(env, lot))
+        .map(|lot| lot.amount)
+        .sum()
+}
+
+/// Create the lot of freshly minted points when expiry is on
+pub fn record_mint(env: &Env, to: &Address, amount: i128) {
+    let lifetime = read_lifetime(env);
+    if lifetime == 0 || amount == 0 {
+        return;
+    }
+
+    let expires_at = env
+        .ledger()
+        .timestamp()
+        .saturating_add(lifetime)
+        .div_ceil(LOT_GRANULARITY_SECONDS)
+        .saturating_mul(LOT_GRANULARITY_SECONDS);
+    give(env, to, None, &Vec::from_array(env, [Lot { amount, expires_at }]));
+}
+
+/// Remove `amount` from the lots of an account, oldest first, and return
+/// the pieces taken. Whatever the lots do not cover comes out of the part
+/// of the balance that never expires. An exempt account takes from the
+/// lots it holds for the recipient `to`.
+pub fn take(env: &Env, id: &Address, amount: i128, to: Option<&Address>) -> Vec<Lot> {
+    let Some(key) = lots_key(env, id, to) else {
+        return Vec::new(env);
+    };
+    let lots = read(env, &key);
+    let mut kept = Vec::new(env);
+    let mut taken = Vec::new(env);
+    let mut left = amount;
+
+    for lot in lots.iter() {
+        if left == 0 {
+            kept.push_back(lot);
+            continue;
+        }
+
+        let piece = lot.amount.min(left);
+        left -= piece;
+        taken.push_back(Lot {
+            amount: piece,
+            expires_at: lot.expires_at,
+        });
+        if piece < lot.amount {
+            kept.push_back(Lot {
+                amount: lot.amount - piece,
+                expires_at: lot.expires_at,
+            });
+        }
+    }
+
+    if !taken.is_empty() {
+        write(env, &key, &kept);
+    }
+    taken
+}
+
+/// Add lots to an account, merging those expiring on the same day. An
+/// exempt account holds them for the sender `from`.
+pub fn give(env: &Env, id: &Address, from: Option<&Address>, pieces: &Vec<Lot>) {
+    if pieces.is_empty() {
+        return;
+    }
+    let Some(key) = lots_key(env, id, from) else {
+        return;
+    };
+
+    let mut lots = read(env, &key);
+    for piece in pieces.iter() {
+        let index = lots
+            .iter()
+            .position(|lot| lot.expires_at >= piece.expires_at)
+            .unwrap_or(lots.len() as usize) as u32;
+
+        match lots.get(index) {
+            Some(lot) if lot.expires_at == piece.expires_at => lots.set(
+                index,
+                Lot {
+                    amount: lot.amount + piece.amount,
+                    expires_at: lot.expires_at,
+                },
+            ),
+            _ => lots.insert(index, piece),
+        }
+    }
+    write(env, &key, &lots);
+}
+
+/// Drop the expired lots of an account and return their total
+pub fn sweep(env: &Env, id: &Address) -> i128 {
+    let lots = read_lots(env, id);
+    let mut kept = Vec::new(env);
+    let mut expired = 0;
+
+    for lot in lots.iter() {
+        if is_expired(env, &lot) {
+            expired += lot.amount;
+        } else {
+            kept.push_back(lot);
+        }
+    }
+
+    if expired > 0 {
+        write(env, &DataKey::Lots(id.clone()), &kept);
+    }
+    expired
+}
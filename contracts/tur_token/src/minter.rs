@@ -0,0 +1,110 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::{DataKey, Error};
+
+// Minting allowed to an authorized minter: `quota` over its lifetime, and
+// at most `rate_limit` within each window of `period_seconds`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterLimits {
+    pub quota: i128,
+    pub rate_limit: i128,
+    pub period_seconds: u64,
+}
+
+// Amount minted by a minter, in total and in the current window
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MinterUsage {
+    pub total_minted: i128,
+    pub period_start: u64,
+    pub period_minted: i128,
+}
+
+impl MinterLimits {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.quota < 0 || self.rate_limit < 0 || self.period_seconds == 0 {
+            return Err(Error::InvalidMinterLimits);
+        }
+        Ok(())
+    }
+}
+
+impl MinterUsage {
+    // Usage as seen at `now`; windows are fixed and start with the first
+    // mint after the previous one ended
+    fn at(&self, limits: &MinterLimits, now: u64) -> MinterUsage {
+        if now >= self.period_start.saturating_add(limits.period_seconds) {
+            return MinterUsage {
+                total_minted: self.total_minted,
+                period_start: now,
+                period_minted: 0,
+            };
+        }
+        self.clone()
+    }
+}
+
+pub fn read_limits(env: &Env, minter: &Address) -> Option<MinterLimits> {
+    env.storage()
+        .instance()
+        .get(&DataKey::MinterLimits(minter.clone()))
+}
+
+pub fn write_limits(env: &Env, minter: &Address, limits: &MinterLimits) {
+    env.storage()
+        .instance()
+        .set(&DataKey::MinterLimits(minter.clone()), limits);
+}
+
+pub fn read_usage(env: &Env, minter: &Address) -> MinterUsage {
+    env.storage()
+        .instance()
+        .get(&DataKey::MinterUsage(minter.clone()))
+        .unwrap_or_default()
+}
+
+/// Amount `minter` can mint right now under its quota and rate limit.
+/// Minters without limits cannot mint.
+pub fn remaining(env: &Env, minter: &Address) -> i128 {
+    let Some(limits) = read_limits(env, minter) else {
+        return 0;
+    };
+    let usage = read_usage(env, minter).at(&limits, env.ledger().timestamp());
+
+    let quota_left = limits.quota - usage.total_minted;
+    let rate_left = limits.rate_limit - usage.period_minted;
+    quota_left.min(rate_left).max(0)
+}
+
+/// Record `amount` minted by `minter`, failing when it goes over the
+/// quota or the rate limit
+pub fn record_mint(env: &Env, minter: &Address, amount: i128) -> Result<(), Error> {
+    if amount < 0 {
+        return Err(Error::NegativeAmount);
+    }
+
+    let limits = read_limits(env, minter).ok_or(Error::MinterQuotaExceeded)?;
+    let mut usage = read_usage(env, minter).at(&limits, env.ledger().timestamp());
+
+    usage.total_minted = usage
+        .total_minted
+        .checked_add(amount)
+        .ok_or(Error::Overflow)?;
+    if usage.total_minted > limits.quota {
+        return Err(Error::MinterQuotaExceeded);
+    }
+
+    usage.period_minted = usage
+        .period_minted
+        .checked_add(amount)
+        .ok_or(Error::Overflow)?;
+    if usage.period_minted > limits.rate_limit {
+        return Err(Error::MinterRateLimited);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::MinterUsage(minter.clone()), &usage);
+    Ok(())
+}
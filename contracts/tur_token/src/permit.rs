@@ -0,0 +1,99 @@
+use soroban_sdk::{
+    contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol,
+};
+
+use crate::{DataKey, Error, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+// Tag that keeps a permit signature from being valid as any other message
+const PERMIT_DOMAIN: Symbol = symbol_short!("TURPERMIT");
+
+// Message signed by the owner for `permit`. The network and the token
+// contract are part of it, so a signature cannot be replayed on another
+// network or another token; the nonce keeps it from being replayed here.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermitPayload {
+    pub domain: Symbol,
+    pub network_id: BytesN<32>,
+    pub contract: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+    pub nonce: u64,
+}
+
+/// Bytes the owner signs: the XDR of the payload
+pub fn message(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u64,
+) -> Bytes {
+    PermitPayload {
+        domain: PERMIT_DOMAIN,
+        network_id: env.ledger().network_id(),
+        contract: env.current_contract_address(),
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount,
+        expiration_ledger,
+        nonce,
+    }
+    .to_xdr(env)
+}
+
+/// Nonce the next permit of `owner` must carry
+pub fn read_nonce(env: &Env, owner: &Address) -> u64 {
+    let key = DataKey::PermitNonce(owner.clone());
+    match env.storage().persistent().get(&key) {
+        Some(nonce) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+            nonce
+        }
+        None => 0,
+    }
+}
+
+// Ed25519 key of an account address. The XDR of an account address is the
+// ScVal and ScAddress discriminants, the key type (0, ed25519) and the key;
+// contract addresses have no key to sign with.
+fn public_key(env: &Env, owner: &Address) -> Result<BytesN<32>, Error> {
+    let xdr = owner.clone().to_xdr(env);
+    if xdr.len() != 44 || xdr.slice(4..12) != Bytes::from_array(env, &[0; 8]) {
+        return Err(Error::InvalidPermitSigner);
+    }
+    Ok(xdr.slice(12..44).try_into().unwrap())
+}
+
+/// Check the owner's signature of a permit and use up its nonce. An
+/// invalid signature fails the call.
+pub fn verify(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u64,
+    signature: &BytesN<64>,
+) -> Result<(), Error> {
+    let expected = read_nonce(env, owner);
+    if nonce != expected {
+        return Err(Error::InvalidPermitNonce);
+    }
+
+    let public_key = public_key(env, owner)?;
+    let message = message(env, owner, spender, amount, expiration_ledger, nonce);
+    env.crypto().ed25519_verify(&public_key, &message, signature);
+
+    let key = DataKey::PermitNonce(owner.clone());
+    env.storage().persistent().set(&key, &(expected + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::{DataKey, Error, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+
+// Privileged roles. Admins manage the roles and hold every power; the
+// other roles each cover one area so it can be delegated on its own.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Role {
+    Admin = 0,             // roles, direct mints and storage migrations
+    MinterManager = 1,     // authorized minters and their limits
+    Pauser = 2,            // pausing and unpausing transfers
+    ComplianceOfficer = 3, // freezes, clawbacks and transfer restrictions
+}
+
+// Grants are per account, so they live in persistent storage like the
+// balances and are kept alive as long as a balance would be
+
+/// Whether `account` is the admin set at initialization
+pub fn is_initial_admin(env: &Env, account: &Address) -> bool {
+    let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+    admin.as_ref() == Some(account)
+}
+
+/// Whether `account` holds `role`. The admin set at initialization is
+/// always an admin, and admins hold every role.
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    if is_initial_admin(env, account) {
+        return true;
+    }
+
+    let holds = |role: Role| {
+        let key = DataKey::Role(role, account.clone());
+        let granted = env.storage().persistent().get(&key).unwrap_or(false);
+        if granted {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        }
+        granted
+    };
+    holds(role) || holds(Role::Admin)
+}
+
+/// Check that `caller` authorized the call and holds `role`
+pub fn require_role(env: &Env, role: Role, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    if !has_role(env, role, caller) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+pub fn write_role(env: &Env, role: Role, account: &Address, granted: bool) {
+    let key = DataKey::Role(role, account.clone());
+    if granted {
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
@@ -0,0 +1,325 @@
+#![cfg(any(test, feature = "testutils"))]
+
+//! Invariant checking harness shared by the property tests and the fuzz
+//! target. It drives a token through arbitrary operations and checks the
+//! contract against a simple model after each one.
+
+extern crate std;
+
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig},
+    Address, Env, String,
+};
+
+use crate::{TurToken, TurTokenClient};
+
+/// Number of accounts operations are drawn from; account 0 is the admin
+pub const ACCOUNTS: usize = 4;
+
+/// Balance of the admin right after initialization
+pub const INITIAL_SUPPLY: i128 = 1_000_000;
+
+/// Cap on the total supply, low enough for random mints to reach it
+pub const MAX_SUPPLY: i128 = 4 * INITIAL_SUPPLY;
+
+/// One token call, with accounts given as indexes into the harness accounts
+#[derive(Clone, Debug)]
+pub enum Op {
+    Mint { to: u8, amount: i128 },
+    Transfer { from: u8, to: u8, amount: i128 },
+    Burn { from: u8, amount: i128 },
+    Approve { owner: u8, spender: u8, amount: i128 },
+    TransferFrom { spender: u8, from: u8, to: u8, amount: i128 },
+    BurnFrom { spender: u8, from: u8, amount: i128 },
+}
+
+// Allowances outlive every operation sequence; the ledger never advances
+const ALLOWANCE_LEDGERS: u32 = 1_000;
+
+// Everything observable about the token for the harness accounts
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct State {
+    total_supply: i128,
+    balances: Vec<i128>,
+    allowances: BTreeMap<(usize, usize), i128>,
+}
+
+pub struct Harness {
+    env: Env,
+    contract_id: Address,
+    accounts: Vec<Address>,
+    model: State,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        // Thousands of environments are created per run, so skip the
+        // snapshot files written on drop
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+
+        let contract_id = env.register(TurToken {}, ());
+        let accounts: Vec<Address> = (0..ACCOUNTS).map(|_| Address::generate(&env)).collect();
+
+        TurTokenClient::new(&env, &contract_id).initialize(
+            &accounts[0],
+            &String::from_str(&env, "Turistas Token"),
+            &String::from_str(&env, "TUR"),
+            &7,
+            &INITIAL_SUPPLY,
+            &MAX_SUPPLY,
+        );
+
+        let mut balances = std::vec![0; ACCOUNTS];
+        balances[0] = INITIAL_SUPPLY;
+        let model = State {
+            total_supply: INITIAL_SUPPLY,
+            balances,
+            allowances: BTreeMap::new(),
+        };
+
+        let harness = Harness {
+            env,
+            contract_id,
+            accounts,
+            model,
+        };
+        harness.check();
+        harness
+    }
+
+    /// Run one operation and check the invariants. Panics when the
+    /// contract diverges from the model or breaks an invariant.
+    pub fn apply(&mut self, op: &Op) {
+        let before = self.read_state();
+        let result = self.call(op);
+        let after = self.read_state();
+
+        match result {
+            Ok(()) => {
+                self.apply_to_model(op);
+                assert_eq!(after, self.model, "state diverged after {op:?}");
+            }
+            Err(()) => {
+                assert_eq!(after, before, "failed {op:?} changed state");
+                assert!(
+                    !self.model_accepts(op),
+                    "{op:?} failed although it should have succeeded"
+                );
+            }
+        }
+
+        self.check();
+    }
+
+    fn client(&self) -> TurTokenClient<'_> {
+        TurTokenClient::new(&self.env, &self.contract_id)
+    }
+
+    fn account(&self, index: u8) -> &Address {
+        &self.accounts[index as usize % ACCOUNTS]
+    }
+
+    fn call(&self, op: &Op) -> Result<(), ()> {
+        let client = self.client();
+        match *op {
+            Op::Mint { to, amount } => {
+                outcome(client.try_mint(&self.accounts[0], self.account(to), &amount))
+            }
+            Op::Transfer { from, to, amount } => {
+                outcome(client.try_transfer(self.account(from), self.account(to), &amount))
+            }
+            Op::Burn { from, amount } => outcome(client.try_burn(self.account(from), &amount)),
+            Op::Approve {
+                owner,
+                spender,
+                amount,
+            } => outcome(client.try_approve(
+                self.account(owner),
+                self.account(spender),
+                &amount,
+                &(self.env.ledger().sequence() + ALLOWANCE_LEDGERS),
+            )),
+            Op::TransferFrom {
+                spender,
+                from,
+                to,
+                amount,
+            } => outcome(client.try_transfer_from(
+                self.account(spender),
+                self.account(from),
+                self.account(to),
+                &amount,
+            )),
+            Op::BurnFrom {
+                spender,
+                from,
+                amount,
+            } => outcome(client.try_burn_from(self.account(spender), self.account(from), &amount)),
+        }
+    }
+
+    fn read_state(&self) -> State {
+        let client = self.client();
+        let mut allowances = BTreeMap::new();
+        for owner in 0..ACCOUNTS {
+            for spender in 0..ACCOUNTS {
+                let allowance = client.allowance(&self.accounts[owner], &self.accounts[spender]);
+                if allowance != 0 {
+                    allowances.insert((owner, spender), allowance);
+                }
+            }
+        }
+
+        State {
+            total_supply: client.total_supply(),
+            balances: self.accounts.iter().map(|id| client.balance(id)).collect(),
+            allowances,
+        }
+    }
+
+    // Whether the operation is valid against the current model
+    fn model_accepts(&self, op: &Op) -> bool {
+        let model = &self.model;
+        let idx = |index: u8| index as usize % ACCOUNTS;
+        match *op {
+            Op::Mint { amount, .. } => {
+                amount >= 0
+                    && model
+                        .total_supply
+                        .checked_add(amount)
+                        .is_some_and(|supply| supply <= MAX_SUPPLY)
+            }
+            Op::Transfer { from, to, amount } => {
+                amount >= 0
+                    && model.balances[idx(from)] >= amount
+                    && (idx(from) == idx(to)
+                        || model.balances[idx(to)].checked_add(amount).is_some())
+            }
+            Op::Burn { from, amount } => amount >= 0 && model.balances[idx(from)] >= amount,
+            Op::Approve { amount, .. } => amount >= 0,
+            Op::TransferFrom {
+                spender,
+                from,
+                to,
+                amount,
+            } => {
+                amount >= 0
+                    && model.balances[idx(from)] >= amount
+                    && model.allowance(idx(from), idx(spender)) >= amount
+                    && (idx(from) == idx(to)
+                        || model.balances[idx(to)].checked_add(amount).is_some())
+            }
+            Op::BurnFrom {
+                spender,
+                from,
+                amount,
+            } => {
+                amount >= 0
+                    && model.balances[idx(from)] >= amount
+                    && model.allowance(idx(from), idx(spender)) >= amount
+            }
+        }
+    }
+
+    fn apply_to_model(&mut self, op: &Op) {
+        let idx = |index: u8| index as usize % ACCOUNTS;
+        let model = &mut self.model;
+        match *op {
+            Op::Mint { to, amount } => {
+                model.balances[idx(to)] += amount;
+                model.total_supply += amount;
+            }
+            Op::Transfer { from, to, amount } => {
+                model.balances[idx(from)] -= amount;
+                model.balances[idx(to)] += amount;
+            }
+            Op::Burn { from, amount } => {
+                model.balances[idx(from)] -= amount;
+                model.total_supply -= amount;
+            }
+            Op::Approve {
+                owner,
+                spender,
+                amount,
+            } => model.set_allowance(idx(owner), idx(spender), amount),
+            Op::TransferFrom {
+                spender,
+                from,
+                to,
+                amount,
+            } => {
+                let allowance = model.allowance(idx(from), idx(spender));
+                model.set_allowance(idx(from), idx(spender), allowance - amount);
+                model.balances[idx(from)] -= amount;
+                model.balances[idx(to)] += amount;
+            }
+            Op::BurnFrom {
+                spender,
+                from,
+                amount,
+            } => {
+                let allowance = model.allowance(idx(from), idx(spender));
+                model.set_allowance(idx(from), idx(spender), allowance - amount);
+                model.balances[idx(from)] -= amount;
+                model.total_supply -= amount;
+            }
+        }
+    }
+
+    // Invariants that must hold after every operation
+    fn check(&self) {
+        let state = self.read_state();
+
+        assert!(
+            state.balances.iter().all(|balance| *balance >= 0),
+            "negative balance: {:?}",
+            state.balances
+        );
+        assert!(
+            state.allowances.values().all(|allowance| *allowance >= 0),
+            "negative allowance: {:?}",
+            state.allowances
+        );
+        assert_eq!(
+            state.balances.iter().sum::<i128>(),
+            state.total_supply,
+            "balances do not add up to the total supply"
+        );
+        assert!(
+            state.total_supply <= MAX_SUPPLY,
+            "total supply {} above the cap",
+            state.total_supply
+        );
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State {
+    fn allowance(&self, owner: usize, spender: usize) -> i128 {
+        self.allowances.get(&(owner, spender)).copied().unwrap_or(0)
+    }
+
+    fn set_allowance(&mut self, owner: usize, spender: usize, amount: i128) {
+        if amount == 0 {
+            self.allowances.remove(&(owner, spender));
+        } else {
+            self.allowances.insert((owner, spender), amount);
+        }
+    }
+}
+
+// Whether a `try_` call succeeded, whatever it returned or failed with
+fn outcome<T, E>(result: Result<T, E>) -> Result<(), ()> {
+    result.map(|_| ()).map_err(|_| ())
+}
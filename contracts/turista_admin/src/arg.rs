@@ -0,0 +1,72 @@
+use serde_json::{json, Value};
+use soroban_sdk::{Address, Env, IntoVal, Map, Symbol, Val, Vec};
+
+/// Contract call argument, convertible for every backend
+#[derive(Clone, Debug, PartialEq)]
+pub enum Arg {
+    Address(String),
+    Bool(bool),
+    U32(u32),
+    U64(u64),
+    I128(i128),
+    String(String),
+    Option(Option<Box<Arg>>),
+    Vec(std::vec::Vec<Arg>),
+    // `#[contracttype]` struct, as (field, value) pairs
+    Struct(std::vec::Vec<(&'static str, Arg)>),
+}
+
+impl Arg {
+    pub fn some(arg: Arg) -> Self {
+        Arg::Option(Some(Box::new(arg)))
+    }
+
+    /// Host value in a soroban environment
+    pub fn to_val(&self, env: &Env) -> Val {
+        match self {
+            Arg::Address(address) => Address::from_str(env, address).into_val(env),
+            Arg::Bool(value) => value.into_val(env),
+            Arg::U32(value) => value.into_val(env),
+            Arg::U64(value) => value.into_val(env),
+            Arg::I128(value) => value.into_val(env),
+            Arg::String(value) => soroban_sdk::String::from_str(env, value).into_val(env),
+            Arg::Option(None) => ().into_val(env),
+            Arg::Option(Some(arg)) => arg.to_val(env),
+            Arg::Vec(items) => {
+                let mut vec = Vec::<Val>::new(env);
+                for item in items {
+                    vec.push_back(item.to_val(env));
+                }
+                vec.into_val(env)
+            }
+            Arg::Struct(fields) => {
+                let mut map = Map::<Symbol, Val>::new(env);
+                for (name, value) in fields {
+                    map.set(Symbol::new(env, name), value.to_val(env));
+                }
+                map.into_val(env)
+            }
+        }
+    }
+
+    /// JSON value as accepted by `stellar contract invoke`
+    pub fn to_json(&self) -> Value {
+        match self {
+            Arg::Address(value) | Arg::String(value) => json!(value),
+            Arg::Bool(value) => json!(value),
+            Arg::U32(value) => json!(value),
+            // 64 and 128 bit integers are passed as strings
+            Arg::U64(value) => json!(value.to_string()),
+            Arg::I128(value) => json!(value.to_string()),
+            Arg::Option(None) => Value::Null,
+            Arg::Option(Some(arg)) => arg.to_json(),
+            Arg::Vec(items) => Value::Array(items.iter().map(Arg::to_json).collect()),
+            Arg::Struct(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_json()))
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{Arg, Error};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Contract {
+    TurToken,
+    CheckinNft,
+    Marketplace,
+    Staking,
+}
+
+impl Contract {
+    /// File name of the contract build
+    pub fn wasm_name(&self) -> &'static str {
+        match self {
+            Contract::TurToken => "tur_token.wasm",
+            Contract::CheckinNft => "checkin_nft.wasm",
+            Contract::Marketplace => "marketplace.wasm",
+            Contract::Staking => "staking.wasm",
+        }
+    }
+}
+
+/// Network the admin commands run against
+pub trait Backend {
+    /// Address of the account signing admin calls
+    fn admin(&mut self) -> Result<String, Error>;
+
+    /// Deploy a contract and return its id
+    fn deploy(&mut self, contract: Contract) -> Result<String, Error>;
+
+    /// Id of the native XLM asset contract
+    fn native_asset(&mut self) -> Result<String, Error>;
+
+    /// Call a contract function as the admin and return its result
+    fn invoke(
+        &mut self,
+        contract_id: &str,
+        function: &str,
+        args: &[(&str, Arg)],
+    ) -> Result<String, Error>;
+}
@@ -0,0 +1,542 @@
+use tur_token::{Role, TransferPolicy};
+
+use crate::{Arg, Backend, Contract, Error};
+
+/// Settings of a fresh deployment
+#[derive(Clone, Debug)]
+pub struct DeployConfig {
+    pub token_name: String,
+    pub token_symbol: String,
+    pub decimals: u32,
+    pub initial_supply: i128,
+    // Cap on the total supply, fixed at deployment
+    pub max_supply: i128,
+    // TUR the check-in contract may mint in total and per day
+    pub checkin_mint_quota: i128,
+    pub checkin_daily_mint_limit: i128,
+    // TUR to stake, and for how long, to reach the silver and gold tiers
+    pub silver_min_stake: i128,
+    pub silver_min_seconds: u64,
+    pub gold_min_stake: i128,
+    pub gold_min_seconds: u64,
+    // How long unstaked TUR stays locked
+    pub unstake_cooldown_seconds: u64,
+    // Receives marketplace fees; defaults to the admin
+    pub platform: Option<String>,
+    // XLM asset contract; defaults to the native asset contract
+    pub xlm_token: Option<String>,
+}
+
+impl Default for DeployConfig {
+    fn default() -> Self {
+        DeployConfig {
+            token_name: "Turistas Token".to_string(),
+            token_symbol: "TUR".to_string(),
+            decimals: 7,
+            initial_supply: 10_000_000_0000000,        // 10M TUR
+            max_supply: 100_000_000_0000000,           // 100M TUR
+            checkin_mint_quota: 50_000_000_0000000,    // 50M TUR
+            checkin_daily_mint_limit: 100_000_0000000, // 100k TUR
+            silver_min_stake: 1_000_0000000,           // 1k TUR
+            silver_min_seconds: 7 * 86_400,
+            gold_min_stake: 10_000_0000000, // 10k TUR
+            gold_min_seconds: 30 * 86_400,
+            unstake_cooldown_seconds: 7 * 86_400,
+            platform: None,
+            xlm_token: None,
+        }
+    }
+}
+
+/// Ids of the deployed contracts
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deployment {
+    pub admin: String,
+    pub tur_token: String,
+    pub checkin_nft: String,
+    pub marketplace: String,
+    pub staking: String,
+    pub xlm_token: String,
+}
+
+impl Deployment {
+    /// Contract ids as the frontend `.env` expects them
+    pub fn env_lines(&self) -> String {
+        format!(
+            "VITE_TUR_TOKEN_CONTRACT={}\nVITE_CHECKIN_NFT_CONTRACT={}\nVITE_MARKETPLACE_CONTRACT={}\nVITE_STAKING_CONTRACT={}\nVITE_XLM_CONTRACT={}",
+            self.tur_token, self.checkin_nft, self.marketplace, self.staking, self.xlm_token
+        )
+    }
+}
+
+/// Deploy the contracts, initialize them and let the check-in contract
+/// mint TUR rewards within its limits. TUR staked in the staking contract
+/// is exempt from expiry.
+pub fn deploy(backend: &mut dyn Backend, config: &DeployConfig) -> Result<Deployment, Error> {
+    let admin = backend.admin()?;
+    let xlm_token = match &config.xlm_token {
+        Some(xlm_token) => xlm_token.clone(),
+        None => backend.native_asset()?,
+    };
+    let platform = config.platform.clone().unwrap_or_else(|| admin.clone());
+
+    let tur_token = backend.deploy(Contract::TurToken)?;
+    let checkin_nft = backend.deploy(Contract::CheckinNft)?;
+    let marketplace = backend.deploy(Contract::Marketplace)?;
+    let staking = backend.deploy(Contract::Staking)?;
+
+    backend.invoke(
+        &tur_token,
+        "initialize",
+        &[
+            ("admin", Arg::Address(admin.clone())),
+            ("name", Arg::String(config.token_name.clone())),
+            ("symbol", Arg::String(config.token_symbol.clone())),
+            ("decimals", Arg::U32(config.decimals)),
+            ("initial_supply", Arg::I128(config.initial_supply)),
+            ("max_supply", Arg::I128(config.max_supply)),
+        ],
+    )?;
+    backend.invoke(
+        &checkin_nft,
+        "initialize",
+        &[
+            ("admin", Arg::Address(admin.clone())),
+            ("tur_token_contract", Arg::Address(tur_token.clone())),
+        ],
+    )?;
+    backend.invoke(
+        &tur_token,
+        "set_authorized_minter",
+        &[
+            ("caller", Arg::Address(admin.clone())),
+            ("minter", Arg::Address(checkin_nft.clone())),
+            ("authorized", Arg::Bool(true)),
+        ],
+    )?;
+    set_minter_limits(
+        backend,
+        &tur_token,
+        &checkin_nft,
+        config.checkin_mint_quota,
+        config.checkin_daily_mint_limit,
+        86_400,
+    )?;
+    backend.invoke(
+        &marketplace,
+        "initialize",
+        &[
+            ("admin", Arg::Address(admin.clone())),
+            ("platform_address", Arg::Address(platform)),
+            ("tur_token_contract", Arg::Address(tur_token.clone())),
+            ("xlm_token_contract", Arg::Address(xlm_token.clone())),
+        ],
+    )?;
+    backend.invoke(
+        &staking,
+        "initialize",
+        &[
+            ("admin", Arg::Address(admin.clone())),
+            ("tur_token_contract", Arg::Address(tur_token.clone())),
+            (
+                "tiers",
+                tier_config(
+                    (config.silver_min_stake, config.silver_min_seconds),
+                    (config.gold_min_stake, config.gold_min_seconds),
+                ),
+            ),
+            (
+                "cooldown_seconds",
+                Arg::U64(config.unstake_cooldown_seconds),
+            ),
+        ],
+    )?;
+    set_expiry_exempt(backend, &tur_token, &staking, true)?;
+
+    Ok(Deployment {
+        admin,
+        tur_token,
+        checkin_nft,
+        marketplace,
+        staking,
+        xlm_token,
+    })
+}
+
+/// Allow or stop a merchant selling through the marketplace
+pub fn set_merchant(
+    backend: &mut dyn Backend,
+    marketplace: &str,
+    merchant: &str,
+    approved: bool,
+) -> Result<(), Error> {
+    backend.invoke(
+        marketplace,
+        "set_merchant",
+        &[
+            ("merchant", Arg::Address(merchant.to_string())),
+            ("approved", Arg::Bool(approved)),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Set the marketplace fee in basis points
+pub fn set_platform_fee(
+    backend: &mut dyn Backend,
+    marketplace: &str,
+    fee_bps: u32,
+) -> Result<(), Error> {
+    backend.invoke(
+        marketplace,
+        "set_platform_fee_bps",
+        &[("fee_bps", Arg::U32(fee_bps))],
+    )?;
+    Ok(())
+}
+
+/// Set the total quota of a TUR minter and how much it may mint per period
+pub fn set_minter_limits(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    minter: &str,
+    quota: i128,
+    rate_limit: i128,
+    period_seconds: u64,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    backend.invoke(
+        tur_token,
+        "set_minter_limits",
+        &[
+            ("caller", Arg::Address(caller)),
+            ("minter", Arg::Address(minter.to_string())),
+            (
+                "limits",
+                Arg::Struct(vec![
+                    ("period_seconds", Arg::U64(period_seconds)),
+                    ("quota", Arg::I128(quota)),
+                    ("rate_limit", Arg::I128(rate_limit)),
+                ]),
+            ),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Move TUR balances still held in the token instance to persistent
+/// storage, for tokens deployed before balances moved out of it
+pub fn migrate_balances(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    holders: &[String],
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    let holders = holders.iter().cloned().map(Arg::Address).collect();
+    backend.invoke(
+        tur_token,
+        "migrate_balances",
+        &[
+            ("caller", Arg::Address(caller)),
+            ("holders", Arg::Vec(holders)),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Grant or revoke a TUR role
+pub fn set_role(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    role: Role,
+    account: &str,
+    granted: bool,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    let function = if granted { "grant_role" } else { "revoke_role" };
+    backend.invoke(
+        tur_token,
+        function,
+        &[
+            ("caller", Arg::Address(caller)),
+            ("role", Arg::U32(role as u32)),
+            ("account", Arg::Address(account.to_string())),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Freeze or unfreeze a TUR account
+pub fn set_frozen(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    account: &str,
+    frozen: bool,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    let function = if frozen { "freeze" } else { "unfreeze" };
+    backend.invoke(
+        tur_token,
+        function,
+        &[
+            ("caller", Arg::Address(caller)),
+            ("account", Arg::Address(account.to_string())),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Move TUR of an account to the treasury
+pub fn clawback(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    from: &str,
+    amount: i128,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    backend.invoke(
+        tur_token,
+        "clawback",
+        &[
+            ("caller", Arg::Address(caller)),
+            ("from", Arg::Address(from.to_string())),
+            ("amount", Arg::I128(amount)),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Set the account clawed back TUR is sent to
+pub fn set_treasury(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    treasury: &str,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    backend.invoke(
+        tur_token,
+        "set_treasury",
+        &[
+            ("caller", Arg::Address(caller)),
+            ("treasury", Arg::Address(treasury.to_string())),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Set who TUR holders may transfer to
+pub fn set_transfer_policy(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    policy: TransferPolicy,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    backend.invoke(
+        tur_token,
+        "set_transfer_policy",
+        &[
+            ("caller", Arg::Address(caller)),
+            ("policy", Arg::U32(policy as u32)),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Allow or stop TUR transfers to an account under the allowlist-only policy
+pub fn set_allowlisted(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    account: &str,
+    allowed: bool,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    backend.invoke(
+        tur_token,
+        "set_allowlisted",
+        &[
+            ("caller", Arg::Address(caller)),
+            ("account", Arg::Address(account.to_string())),
+            ("allowed", Arg::Bool(allowed)),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Stop or resume tracking TUR expiry for an account, e.g. a pool contract
+pub fn set_expiry_exempt(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    account: &str,
+    exempt: bool,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    backend.invoke(
+        tur_token,
+        "set_expiry_exempt",
+        &[
+            ("caller", Arg::Address(caller)),
+            ("account", Arg::Address(account.to_string())),
+            ("exempt", Arg::Bool(exempt)),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Make TUR minted from now on expire after `lifetime_seconds`, or never
+/// with 0
+pub fn set_points_lifetime(
+    backend: &mut dyn Backend,
+    tur_token: &str,
+    lifetime_seconds: u64,
+) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    backend.invoke(
+        tur_token,
+        "set_points_lifetime",
+        &[
+            ("caller", Arg::Address(caller)),
+            ("lifetime_seconds", Arg::U64(lifetime_seconds)),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Pause or resume TUR transfers, burns and mints
+pub fn set_paused(backend: &mut dyn Backend, tur_token: &str, paused: bool) -> Result<(), Error> {
+    let caller = backend.admin()?;
+    let function = if paused { "pause" } else { "unpause" };
+    backend.invoke(tur_token, function, &[("caller", Arg::Address(caller))])?;
+    Ok(())
+}
+
+/// Vesting of a place's check-in rewards; `None` removes the override
+pub fn set_place_vesting(
+    backend: &mut dyn Backend,
+    checkin_nft: &str,
+    place_id: u32,
+    vesting: Option<(u64, u64)>,
+) -> Result<(), Error> {
+    let config = match vesting {
+        Some((cliff_seconds, duration_seconds)) => Arg::some(Arg::Struct(vec![
+            ("cliff_seconds", Arg::U64(cliff_seconds)),
+            ("duration_seconds", Arg::U64(duration_seconds)),
+        ])),
+        None => Arg::Option(None),
+    };
+
+    backend.invoke(
+        checkin_nft,
+        "set_place_vesting_config",
+        &[("place_id", Arg::U32(place_id)), ("config", config)],
+    )?;
+    Ok(())
+}
+
+/// Level tier of a place, parsed from `min_visits:min_age_seconds:image_url`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LevelTier {
+    pub min_visits: u32,
+    pub min_age_seconds: u64,
+    pub image_url: String,
+}
+
+impl std::str::FromStr for LevelTier {
+    type Err = Error;
+
+    fn from_str(tier: &str) -> Result<Self, Error> {
+        let invalid = || {
+            Error::InvalidArgument(format!(
+                "level tier `{tier}` is not min_visits:min_age_seconds:image_url"
+            ))
+        };
+
+        let mut parts = tier.splitn(3, ':');
+        let min_visits = parts
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(invalid)?;
+        let min_age_seconds = parts
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(invalid)?;
+        let image_url = parts
+            .next()
+            .filter(|url| !url.is_empty())
+            .ok_or_else(invalid)?;
+
+        Ok(LevelTier {
+            min_visits,
+            min_age_seconds,
+            image_url: image_url.to_string(),
+        })
+    }
+}
+
+/// Replace the level tiers of a place; no tiers removes them
+pub fn set_place_levels(
+    backend: &mut dyn Backend,
+    checkin_nft: &str,
+    place_id: u32,
+    tiers: &[LevelTier],
+) -> Result<(), Error> {
+    let tiers = tiers
+        .iter()
+        .map(|tier| {
+            Arg::Struct(vec![
+                ("image_url", Arg::String(tier.image_url.clone())),
+                ("min_age_seconds", Arg::U64(tier.min_age_seconds)),
+                ("min_visits", Arg::U32(tier.min_visits)),
+            ])
+        })
+        .collect();
+
+    backend.invoke(
+        checkin_nft,
+        "set_place_levels",
+        &[("place_id", Arg::U32(place_id)), ("tiers", Arg::Vec(tiers))],
+    )?;
+    Ok(())
+}
+
+// Staking tier thresholds, each as (min_stake, min_seconds)
+fn tier_config(silver: (i128, u64), gold: (i128, u64)) -> Arg {
+    let threshold = |(min_stake, min_seconds)| {
+        Arg::Struct(vec![
+            ("min_stake", Arg::I128(min_stake)),
+            ("min_seconds", Arg::U64(min_seconds)),
+        ])
+    };
+    Arg::Struct(vec![
+        ("silver", threshold(silver)),
+        ("gold", threshold(gold)),
+    ])
+}
+
+/// Replace the staking tier thresholds, each as (min_stake, min_seconds)
+pub fn set_tier_config(
+    backend: &mut dyn Backend,
+    staking: &str,
+    silver: (i128, u64),
+    gold: (i128, u64),
+) -> Result<(), Error> {
+    backend.invoke(
+        staking,
+        "set_tier_config",
+        &[("tiers", tier_config(silver, gold))],
+    )?;
+    Ok(())
+}
+
+/// Set how long unstaked TUR stays locked
+pub fn set_cooldown(
+    backend: &mut dyn Backend,
+    staking: &str,
+    cooldown_seconds: u64,
+) -> Result<(), Error> {
+    backend.invoke(
+        staking,
+        "set_cooldown",
+        &[("cooldown_seconds", Arg::U64(cooldown_seconds))],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use core::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    // External command that exited with an error
+    Command { command: String, stderr: String },
+    // Contract call that failed or returned a contract error
+    Invocation { function: String, reason: String },
+    InvalidArgument(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "io error: {err}"),
+            Error::Command { command, stderr } => write!(f, "`{command}` failed: {stderr}"),
+            Error::Invocation { function, reason } => write!(f, "{function} failed: {reason}"),
+            Error::InvalidArgument(reason) => write!(f, "invalid argument: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
@@ -0,0 +1,25 @@
+//! Admin tooling for the Turista contracts.
+//!
+//! Commands are written against the [`Backend`] trait so the same
+//! deployment and configuration steps run on a network through the
+//! `stellar` CLI ([`StellarCli`]) or natively in a soroban test
+//! environment ([`Sandbox`]).
+
+mod arg;
+mod backend;
+mod commands;
+mod error;
+mod sandbox;
+mod stellar_cli;
+
+pub use arg::Arg;
+pub use backend::{Backend, Contract};
+pub use commands::{
+    clawback, deploy, migrate_balances, set_allowlisted, set_cooldown, set_expiry_exempt,
+    set_frozen, set_merchant, set_minter_limits, set_paused, set_place_levels, set_place_vesting,
+    set_platform_fee, set_points_lifetime, set_role, set_tier_config, set_transfer_policy,
+    set_treasury, DeployConfig, Deployment, LevelTier,
+};
+pub use error::Error;
+pub use sandbox::Sandbox;
+pub use stellar_cli::StellarCli;
@@ -0,0 +1,531 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use tur_token::{Role, TransferPolicy};
+use turista_admin::{
+    clawback, deploy, migrate_balances, set_allowlisted, set_cooldown, set_expiry_exempt,
+    set_frozen, set_merchant, set_minter_limits, set_paused, set_place_levels, set_place_vesting,
+    set_platform_fee, set_points_lifetime, set_role, set_tier_config, set_transfer_policy,
+    set_treasury, Backend, DeployConfig, Error, LevelTier, Sandbox, StellarCli,
+};
+
+/// Deploy and configure the Turista contracts
+#[derive(Parser)]
+#[command(name = "turista-admin")]
+struct Cli {
+    /// Run against a throwaway soroban test environment instead of a network
+    #[arg(long)]
+    sandbox: bool,
+
+    /// Network name known to the stellar CLI
+    #[arg(long, env = "STELLAR_NETWORK", default_value = "testnet")]
+    network: String,
+
+    /// Admin key name or secret key used to sign
+    #[arg(long, env = "STELLAR_ACCOUNT", default_value = "admin")]
+    source: String,
+
+    /// Path of the stellar CLI binary
+    #[arg(long, default_value = "stellar")]
+    stellar: String,
+
+    /// Directory holding the contract builds
+    #[arg(long, default_value = "target/wasm32v1-none/release")]
+    wasm_dir: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Deploy, initialize and wire the contracts
+    Deploy {
+        /// Address receiving marketplace fees (defaults to the admin)
+        #[arg(long)]
+        platform: Option<String>,
+        /// XLM asset contract (defaults to the native asset contract)
+        #[arg(long)]
+        xlm_token: Option<String>,
+        /// Initial TUR supply minted to the admin, in stroops
+        #[arg(long)]
+        initial_supply: Option<i128>,
+        /// Cap on the TUR supply, in stroops; cannot be changed later
+        #[arg(long)]
+        max_supply: Option<i128>,
+        /// Marketplace fee in basis points
+        #[arg(long)]
+        fee_bps: Option<u32>,
+        /// Merchants to register
+        #[arg(long = "merchant")]
+        merchants: Vec<String>,
+    },
+    /// Register or remove marketplace merchants
+    Merchant {
+        #[command(flatten)]
+        contracts: ContractIds,
+        #[command(subcommand)]
+        action: MerchantAction,
+    },
+    /// Set the marketplace fee in basis points
+    Fee {
+        #[command(flatten)]
+        contracts: ContractIds,
+        fee_bps: u32,
+    },
+    /// Configure places of the check-in contract
+    Place {
+        #[command(flatten)]
+        contracts: ContractIds,
+        #[command(subcommand)]
+        action: PlaceAction,
+    },
+    /// Set how much TUR a minter may mint in total and per period
+    MinterLimits {
+        #[command(flatten)]
+        contracts: ContractIds,
+        minter: String,
+        /// Total TUR the minter may mint, in stroops
+        #[arg(long)]
+        quota: i128,
+        /// TUR the minter may mint per period, in stroops
+        #[arg(long)]
+        rate_limit: i128,
+        /// Length of a rate limit period in seconds
+        #[arg(long, default_value_t = 86_400)]
+        period: u64,
+    },
+    /// Grant or revoke TUR roles
+    Role {
+        #[command(flatten)]
+        contracts: ContractIds,
+        #[command(subcommand)]
+        action: RoleAction,
+    },
+    /// Stop TUR transfers, burns and mints
+    Pause {
+        #[command(flatten)]
+        contracts: ContractIds,
+    },
+    /// Resume TUR transfers, burns and mints
+    Unpause {
+        #[command(flatten)]
+        contracts: ContractIds,
+    },
+    /// Block a TUR account from transferring and burning
+    Freeze {
+        #[command(flatten)]
+        contracts: ContractIds,
+        account: String,
+    },
+    /// Lift the freeze of a TUR account
+    Unfreeze {
+        #[command(flatten)]
+        contracts: ContractIds,
+        account: String,
+    },
+    /// Move TUR of an account to the treasury
+    Clawback {
+        #[command(flatten)]
+        contracts: ContractIds,
+        account: String,
+        /// Amount in stroops
+        amount: i128,
+    },
+    /// Set who TUR holders may transfer to
+    TransferPolicy {
+        #[command(flatten)]
+        contracts: ContractIds,
+        policy: PolicyName,
+    },
+    /// Add or remove recipients allowed under the allowlist-only policy
+    Allowlist {
+        #[command(flatten)]
+        contracts: ContractIds,
+        #[command(subcommand)]
+        action: AllowlistAction,
+    },
+    /// Set the account receiving clawed back TUR
+    Treasury {
+        #[command(flatten)]
+        contracts: ContractIds,
+        treasury: String,
+    },
+    /// Make TUR minted from now on expire after a number of days
+    PointsLifetime {
+        #[command(flatten)]
+        contracts: ContractIds,
+        /// Days minted TUR stays usable; 0 turns expiry off
+        days: u64,
+    },
+    /// Stop TUR from expiring while held by an account, e.g. a pool contract
+    ExpiryExempt {
+        #[command(flatten)]
+        contracts: ContractIds,
+        #[command(subcommand)]
+        action: ExpiryExemptAction,
+    },
+    /// Configure the staking tiers and unbonding cooldown
+    Staking {
+        #[command(flatten)]
+        contracts: ContractIds,
+        #[command(subcommand)]
+        action: StakingAction,
+    },
+    /// Move TUR balances out of the token instance storage
+    MigrateBalances {
+        #[command(flatten)]
+        contracts: ContractIds,
+        /// Holders whose balances to move
+        #[arg(required = true)]
+        holders: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MerchantAction {
+    Add { merchant: String },
+    Remove { merchant: String },
+}
+
+#[derive(Subcommand)]
+enum RoleAction {
+    Grant { role: RoleName, account: String },
+    Revoke { role: RoleName, account: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RoleName {
+    Admin,
+    MinterManager,
+    Pauser,
+    ComplianceOfficer,
+}
+
+impl From<RoleName> for Role {
+    fn from(role: RoleName) -> Self {
+        match role {
+            RoleName::Admin => Role::Admin,
+            RoleName::MinterManager => Role::MinterManager,
+            RoleName::Pauser => Role::Pauser,
+            RoleName::ComplianceOfficer => Role::ComplianceOfficer,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PolicyName {
+    Transferable,
+    AllowlistOnly,
+    Locked,
+}
+
+impl From<PolicyName> for TransferPolicy {
+    fn from(policy: PolicyName) -> Self {
+        match policy {
+            PolicyName::Transferable => TransferPolicy::Transferable,
+            PolicyName::AllowlistOnly => TransferPolicy::AllowlistOnly,
+            PolicyName::Locked => TransferPolicy::Locked,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum AllowlistAction {
+    Add { account: String },
+    Remove { account: String },
+}
+
+#[derive(Subcommand)]
+enum ExpiryExemptAction {
+    Add { account: String },
+    Remove { account: String },
+}
+
+#[derive(Subcommand)]
+enum StakingAction {
+    /// Replace the TUR stake and duration needed for each tier
+    Tiers {
+        /// TUR to stake for silver, in stroops
+        #[arg(long)]
+        silver_min_stake: i128,
+        /// Seconds to stay staked for silver
+        #[arg(long)]
+        silver_min_seconds: u64,
+        /// TUR to stake for gold, in stroops
+        #[arg(long)]
+        gold_min_stake: i128,
+        /// Seconds to stay staked for gold
+        #[arg(long)]
+        gold_min_seconds: u64,
+    },
+    /// Set how long unstaked TUR stays locked, in seconds
+    Cooldown { seconds: u64 },
+}
+
+#[derive(Subcommand)]
+enum PlaceAction {
+    /// Vest the check-in rewards of a place
+    Vesting {
+        place_id: u32,
+        #[arg(long, required_unless_present = "clear")]
+        cliff: Option<u64>,
+        #[arg(long, required_unless_present = "clear")]
+        duration: Option<u64>,
+        /// Remove the place override
+        #[arg(long, conflicts_with_all = ["cliff", "duration"])]
+        clear: bool,
+    },
+    /// Replace the level tiers of a place
+    Levels {
+        place_id: u32,
+        /// Tiers as min_visits:min_age_seconds:image_url; none removes them
+        tiers: Vec<LevelTier>,
+    },
+}
+
+// Ids of the deployed contracts; ignored in sandbox mode, which deploys
+// fresh contracts first
+#[derive(Args)]
+struct ContractIds {
+    #[arg(long, env = "VITE_TUR_TOKEN_CONTRACT", default_value = "")]
+    tur_token: String,
+    #[arg(long, env = "VITE_CHECKIN_NFT_CONTRACT", default_value = "")]
+    checkin_nft: String,
+    #[arg(long, env = "VITE_MARKETPLACE_CONTRACT", default_value = "")]
+    marketplace: String,
+    #[arg(long, env = "VITE_STAKING_CONTRACT", default_value = "")]
+    staking: String,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let mut backend: Box<dyn Backend> = if cli.sandbox {
+        Box::new(Sandbox::new())
+    } else {
+        Box::new(StellarCli {
+            binary: cli.stellar,
+            network: cli.network,
+            source: cli.source,
+            wasm_dir: cli.wasm_dir,
+        })
+    };
+
+    match run(backend.as_mut(), cli.sandbox, cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(backend: &mut dyn Backend, sandbox: bool, command: Command) -> Result<(), Error> {
+    match command {
+        Command::Deploy {
+            platform,
+            xlm_token,
+            initial_supply,
+            max_supply,
+            fee_bps,
+            merchants,
+        } => {
+            let mut config = DeployConfig {
+                platform,
+                xlm_token,
+                ..DeployConfig::default()
+            };
+            if let Some(initial_supply) = initial_supply {
+                config.initial_supply = initial_supply;
+            }
+            if let Some(max_supply) = max_supply {
+                config.max_supply = max_supply;
+            }
+
+            let deployment = deploy(backend, &config)?;
+            if let Some(fee_bps) = fee_bps {
+                set_platform_fee(backend, &deployment.marketplace, fee_bps)?;
+            }
+            for merchant in &merchants {
+                set_merchant(backend, &deployment.marketplace, merchant, true)?;
+            }
+
+            println!("{}", deployment.env_lines());
+            Ok(())
+        }
+        Command::Merchant { contracts, action } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            match action {
+                MerchantAction::Add { merchant } => {
+                    set_merchant(backend, &contracts.marketplace, &merchant, true)
+                }
+                MerchantAction::Remove { merchant } => {
+                    set_merchant(backend, &contracts.marketplace, &merchant, false)
+                }
+            }
+        }
+        Command::Fee { contracts, fee_bps } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_platform_fee(backend, &contracts.marketplace, fee_bps)
+        }
+        Command::MinterLimits {
+            contracts,
+            minter,
+            quota,
+            rate_limit,
+            period,
+        } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_minter_limits(
+                backend,
+                &contracts.tur_token,
+                &minter,
+                quota,
+                rate_limit,
+                period,
+            )
+        }
+        Command::Role { contracts, action } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            match action {
+                RoleAction::Grant { role, account } => {
+                    set_role(backend, &contracts.tur_token, role.into(), &account, true)
+                }
+                RoleAction::Revoke { role, account } => {
+                    set_role(backend, &contracts.tur_token, role.into(), &account, false)
+                }
+            }
+        }
+        Command::Pause { contracts } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_paused(backend, &contracts.tur_token, true)
+        }
+        Command::Unpause { contracts } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_paused(backend, &contracts.tur_token, false)
+        }
+        Command::Freeze { contracts, account } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_frozen(backend, &contracts.tur_token, &account, true)
+        }
+        Command::Unfreeze { contracts, account } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_frozen(backend, &contracts.tur_token, &account, false)
+        }
+        Command::Clawback {
+            contracts,
+            account,
+            amount,
+        } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            clawback(backend, &contracts.tur_token, &account, amount)
+        }
+        Command::TransferPolicy { contracts, policy } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_transfer_policy(backend, &contracts.tur_token, policy.into())
+        }
+        Command::Allowlist { contracts, action } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            match action {
+                AllowlistAction::Add { account } => {
+                    set_allowlisted(backend, &contracts.tur_token, &account, true)
+                }
+                AllowlistAction::Remove { account } => {
+                    set_allowlisted(backend, &contracts.tur_token, &account, false)
+                }
+            }
+        }
+        Command::Treasury {
+            contracts,
+            treasury,
+        } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_treasury(backend, &contracts.tur_token, &treasury)
+        }
+        Command::PointsLifetime { contracts, days } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            set_points_lifetime(backend, &contracts.tur_token, days.saturating_mul(86_400))
+        }
+        Command::ExpiryExempt { contracts, action } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            match action {
+                ExpiryExemptAction::Add { account } => {
+                    set_expiry_exempt(backend, &contracts.tur_token, &account, true)
+                }
+                ExpiryExemptAction::Remove { account } => {
+                    set_expiry_exempt(backend, &contracts.tur_token, &account, false)
+                }
+            }
+        }
+        Command::Staking { contracts, action } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            match action {
+                StakingAction::Tiers {
+                    silver_min_stake,
+                    silver_min_seconds,
+                    gold_min_stake,
+                    gold_min_seconds,
+                } => set_tier_config(
+                    backend,
+                    &contracts.staking,
+                    (silver_min_stake, silver_min_seconds),
+                    (gold_min_stake, gold_min_seconds),
+                ),
+                StakingAction::Cooldown { seconds } => {
+                    set_cooldown(backend, &contracts.staking, seconds)
+                }
+            }
+        }
+        Command::MigrateBalances { contracts, holders } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            migrate_balances(backend, &contracts.tur_token, &holders)
+        }
+        Command::Place { contracts, action } => {
+            let contracts = resolve(backend, sandbox, contracts)?;
+            match action {
+                PlaceAction::Vesting {
+                    place_id,
+                    cliff,
+                    duration,
+                    clear,
+                } => {
+                    let vesting = if clear { None } else { cliff.zip(duration) };
+                    set_place_vesting(backend, &contracts.checkin_nft, place_id, vesting)
+                }
+                PlaceAction::Levels { place_id, tiers } => {
+                    set_place_levels(backend, &contracts.checkin_nft, place_id, &tiers)
+                }
+            }
+        }
+    }
+}
+
+fn resolve(
+    backend: &mut dyn Backend,
+    sandbox: bool,
+    contracts: ContractIds,
+) -> Result<ContractIds, Error> {
+    if sandbox {
+        let deployment = deploy(backend, &DeployConfig::default())?;
+        return Ok(ContractIds {
+            tur_token: deployment.tur_token,
+            checkin_nft: deployment.checkin_nft,
+            marketplace: deployment.marketplace,
+            staking: deployment.staking,
+        });
+    }
+
+    if contracts.tur_token.is_empty()
+        || contracts.checkin_nft.is_empty()
+        || contracts.marketplace.is_empty()
+        || contracts.staking.is_empty()
+    {
+        return Err(Error::InvalidArgument(
+            "set --tur-token, --checkin-nft, --marketplace and --staking or their VITE_* variables"
+                .to_string(),
+        ));
+    }
+    Ok(contracts)
+}
@@ -0,0 +1,103 @@
+use checkin_nft::CheckinNFTContract;
+use marketplace::Marketplace;
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig},
+    xdr::{ScAddress, ScVal},
+    Address, Env, Symbol, TryFromVal, Val, Vec,
+};
+use staking::Staking;
+use tur_token::TurToken;
+
+use crate::{Arg, Backend, Contract, Error};
+
+/// Runs the contracts natively in a soroban test environment, with every
+/// authorization mocked. Nothing outlives the process, which makes it a
+/// dry run of the commands.
+pub struct Sandbox {
+    env: Env,
+    admin: Address,
+}
+
+impl Sandbox {
+    pub fn new() -> Self {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+
+        Sandbox { env, admin }
+    }
+
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for Sandbox {
+    fn admin(&mut self) -> Result<String, Error> {
+        Ok(strkey(&self.admin))
+    }
+
+    fn deploy(&mut self, contract: Contract) -> Result<String, Error> {
+        let id = match contract {
+            Contract::TurToken => self.env.register(TurToken, ()),
+            Contract::CheckinNft => self.env.register(CheckinNFTContract, ()),
+            Contract::Marketplace => self.env.register(Marketplace, ()),
+            Contract::Staking => self.env.register(Staking, ()),
+        };
+        Ok(strkey(&id))
+    }
+
+    fn native_asset(&mut self) -> Result<String, Error> {
+        let sac = self
+            .env
+            .register_stellar_asset_contract_v2(self.admin.clone());
+        Ok(strkey(&sac.address()))
+    }
+
+    fn invoke(
+        &mut self,
+        contract_id: &str,
+        function: &str,
+        args: &[(&str, Arg)],
+    ) -> Result<String, Error> {
+        let env = &self.env;
+        let mut vals = Vec::<Val>::new(env);
+        for (_, arg) in args {
+            vals.push_back(arg.to_val(env));
+        }
+
+        let result = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            &Address::from_str(env, contract_id),
+            &Symbol::new(env, function),
+            vals,
+        );
+
+        match result {
+            Ok(Ok(val)) => Ok(ScVal::try_from_val(env, &val)
+                .map(|val| format!("{val:?}"))
+                .unwrap_or_default()),
+            Ok(Err(err)) => Err(invocation_error(function, format!("{err:?}"))),
+            Err(Ok(err)) => Err(invocation_error(function, format!("{err:?}"))),
+            Err(Err(err)) => Err(invocation_error(function, format!("{err:?}"))),
+        }
+    }
+}
+
+fn invocation_error(function: &str, reason: String) -> Error {
+    Error::Invocation {
+        function: function.to_string(),
+        reason,
+    }
+}
+
+fn strkey(address: &Address) -> String {
+    ScAddress::from(address).to_string()
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::{Arg, Backend, Contract, Error};
+
+/// Shells out to the `stellar` CLI, signing with one of its keys
+pub struct StellarCli {
+    pub binary: String,
+    pub network: String,
+    // Key name or secret key passed as `--source`
+    pub source: String,
+    // Directory holding the contract builds
+    pub wasm_dir: PathBuf,
+}
+
+impl StellarCli {
+    /// Arguments of `stellar contract invoke` for a call
+    pub fn invoke_args(
+        &self,
+        contract_id: &str,
+        function: &str,
+        args: &[(&str, Arg)],
+    ) -> Vec<String> {
+        let mut cli_args: Vec<String> = [
+            "contract",
+            "invoke",
+            "--id",
+            contract_id,
+            "--source",
+            &self.source,
+            "--network",
+            &self.network,
+            "--send",
+            "yes",
+            "--",
+            function,
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+
+        for (name, arg) in args {
+            match arg.to_json() {
+                // Unset options are left out, and booleans are flags
+                Value::Null | Value::Bool(false) => {}
+                Value::Bool(true) => cli_args.push(format!("--{name}")),
+                Value::String(value) => {
+                    cli_args.push(format!("--{name}"));
+                    cli_args.push(value);
+                }
+                value => {
+                    cli_args.push(format!("--{name}"));
+                    cli_args.push(value.to_string());
+                }
+            }
+        }
+
+        cli_args
+    }
+
+    fn run(&self, args: &[String]) -> Result<String, Error> {
+        let output = Command::new(&self.binary).args(args).output()?;
+
+        if !output.status.success() {
+            return Err(Error::Command {
+                command: format!("{} {}", self.binary, args.join(" ")),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+impl Backend for StellarCli {
+    fn admin(&mut self) -> Result<String, Error> {
+        self.run(&["keys".into(), "address".into(), self.source.clone()])
+    }
+
+    fn deploy(&mut self, contract: Contract) -> Result<String, Error> {
+        let wasm = self.wasm_dir.join(contract.wasm_name());
+        self.run(&[
+            "contract".into(),
+            "deploy".into(),
+            "--wasm".into(),
+            wasm.display().to_string(),
+            "--source".into(),
+            self.source.clone(),
+            "--network".into(),
+            self.network.clone(),
+        ])
+    }
+
+    fn native_asset(&mut self) -> Result<String, Error> {
+        self.run(&[
+            "contract".into(),
+            "id".into(),
+            "asset".into(),
+            "--asset".into(),
+            "native".into(),
+            "--network".into(),
+            self.network.clone(),
+        ])
+    }
+
+    fn invoke(
+        &mut self,
+        contract_id: &str,
+        function: &str,
+        args: &[(&str, Arg)],
+    ) -> Result<String, Error> {
+        self.run(&self.invoke_args(contract_id, function, args))
+    }
+}
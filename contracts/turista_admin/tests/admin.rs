@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+
+use checkin_nft::CheckinNFTContractClient;
+use marketplace::MarketplaceClient;
+use soroban_sdk::{testutils::Address as _, xdr::ScAddress, Address, String};
+use staking::StakingClient;
+use tur_token::{Role, TransferPolicy, TurTokenClient};
+use turista_admin::{
+    clawback, deploy, migrate_balances, set_allowlisted, set_cooldown, set_expiry_exempt,
+    set_frozen, set_merchant, set_paused, set_place_levels, set_place_vesting, set_platform_fee,
+    set_points_lifetime, set_role, set_tier_config, set_transfer_policy, set_treasury, Arg,
+    DeployConfig, LevelTier, Sandbox, StellarCli,
+};
+
+#[test]
+fn test_deploy_and_configure_in_sandbox() {
+    let mut sandbox = Sandbox::new();
+    let config = DeployConfig::default();
+    let deployment = deploy(&mut sandbox, &config).unwrap();
+
+    let merchant_id = ScAddress::from(&Address::generate(sandbox.env())).to_string();
+
+    set_merchant(&mut sandbox, &deployment.marketplace, &merchant_id, true).unwrap();
+    set_platform_fee(&mut sandbox, &deployment.marketplace, 250).unwrap();
+    set_place_vesting(&mut sandbox, &deployment.checkin_nft, 7, Some((60, 3_600))).unwrap();
+    set_place_levels(
+        &mut sandbox,
+        &deployment.checkin_nft,
+        7,
+        &["3:0:ipfs://silver".parse::<LevelTier>().unwrap()],
+    )
+    .unwrap();
+
+    // Balances of fresh deployments are already out of instance storage
+    migrate_balances(
+        &mut sandbox,
+        &deployment.tur_token,
+        std::slice::from_ref(&merchant_id),
+    )
+    .unwrap();
+
+    set_role(
+        &mut sandbox,
+        &deployment.tur_token,
+        Role::Pauser,
+        &merchant_id,
+        true,
+    )
+    .unwrap();
+    set_paused(&mut sandbox, &deployment.tur_token, true).unwrap();
+    set_paused(&mut sandbox, &deployment.tur_token, false).unwrap();
+    set_treasury(&mut sandbox, &deployment.tur_token, &merchant_id).unwrap();
+    set_frozen(&mut sandbox, &deployment.tur_token, &deployment.admin, true).unwrap();
+    clawback(
+        &mut sandbox,
+        &deployment.tur_token,
+        &deployment.admin,
+        1_0000000,
+    )
+    .unwrap();
+    set_points_lifetime(&mut sandbox, &deployment.tur_token, 365 * 86_400).unwrap();
+    set_transfer_policy(
+        &mut sandbox,
+        &deployment.tur_token,
+        TransferPolicy::AllowlistOnly,
+    )
+    .unwrap();
+    set_allowlisted(
+        &mut sandbox,
+        &deployment.tur_token,
+        &deployment.marketplace,
+        true,
+    )
+    .unwrap();
+    set_expiry_exempt(&mut sandbox, &deployment.tur_token, &merchant_id, true).unwrap();
+    set_tier_config(
+        &mut sandbox,
+        &deployment.staking,
+        (500_0000000, 86_400),
+        (5_000_0000000, 14 * 86_400),
+    )
+    .unwrap();
+    set_cooldown(&mut sandbox, &deployment.staking, 3 * 86_400).unwrap();
+
+    // Invalid settings surface the contract error
+    assert!(set_platform_fee(&mut sandbox, &deployment.marketplace, 10_001).is_err());
+    assert!(set_tier_config(&mut sandbox, &deployment.staking, (500_0000000, 0), (0, 0)).is_err());
+
+    let env = sandbox.env();
+    let address = |id: &str| Address::from_str(env, id);
+    let tur = TurTokenClient::new(env, &address(&deployment.tur_token));
+    let nft = CheckinNFTContractClient::new(env, &address(&deployment.checkin_nft));
+    let marketplace = MarketplaceClient::new(env, &address(&deployment.marketplace));
+    let staking = StakingClient::new(env, &address(&deployment.staking));
+
+    assert_eq!(tur.total_supply(), config.initial_supply);
+    assert_eq!(tur.max_supply(), config.max_supply);
+    assert_eq!(
+        tur.balance(&address(&deployment.admin)),
+        config.initial_supply - 1_0000000
+    );
+    assert_eq!(tur.balance(&address(&merchant_id)), 1_0000000);
+    assert!(tur.is_frozen(&address(&deployment.admin)));
+    assert!(marketplace.is_merchant(&address(&merchant_id)));
+    assert!(tur.has_role(&Role::Pauser, &address(&merchant_id)));
+    assert!(!tur.is_paused());
+    assert_eq!(tur.points_lifetime(), 365 * 86_400);
+    assert_eq!(tur.transfer_policy(), TransferPolicy::AllowlistOnly);
+    assert!(tur.is_allowlisted(&address(&deployment.marketplace)));
+    assert!(tur.is_expiry_exempt(&address(&deployment.staking)));
+    assert!(tur.is_expiry_exempt(&address(&merchant_id)));
+    assert_eq!(marketplace.get_platform_fee_bps(), 250);
+    assert_eq!(staking.get_tier_config().silver.min_stake, 500_0000000);
+    assert_eq!(staking.get_tier_config().gold.min_seconds, 14 * 86_400);
+    assert_eq!(staking.get_cooldown(), 3 * 86_400);
+    assert_eq!(nft.get_vesting_config(&7).unwrap().duration_seconds, 3_600);
+    assert_eq!(nft.get_place_levels(&7).len(), 1);
+
+    // The check-in contract was allowed to mint rewards
+    let traveler = Address::generate(env);
+    nft.mint(
+        &traveler,
+        &1,
+        &String::from_str(env, "Plaza de Armas"),
+        &-13516_500,
+        &-71978_600,
+        &String::from_str(env, "ipfs://plaza"),
+        &None,
+    );
+    assert_eq!(tur.balance(&traveler), checkin_nft::TUR_REWARD_AMOUNT);
+    assert_eq!(
+        tur.remaining_minter_allowance(&nft.address),
+        config.checkin_daily_mint_limit - checkin_nft::TUR_REWARD_AMOUNT
+    );
+}
+
+#[test]
+fn test_stellar_cli_invoke_args() {
+    let cli = StellarCli {
+        binary: "stellar".to_string(),
+        network: "testnet".to_string(),
+        source: "admin".to_string(),
+        wasm_dir: PathBuf::from("target"),
+    };
+
+    let args = cli.invoke_args(
+        "CCONTRACT",
+        "set_place_vesting_config",
+        &[
+            ("place_id", Arg::U32(7)),
+            (
+                "config",
+                Arg::some(Arg::Struct(vec![
+                    ("cliff_seconds", Arg::U64(60)),
+                    ("duration_seconds", Arg::U64(3_600)),
+                ])),
+            ),
+            ("authorized", Arg::Bool(true)),
+            ("hidden", Arg::Bool(false)),
+            ("image_url", Arg::Option(None)),
+        ],
+    );
+
+    assert_eq!(
+        args,
+        [
+            "contract",
+            "invoke",
+            "--id",
+            "CCONTRACT",
+            "--source",
+            "admin",
+            "--network",
+            "testnet",
+            "--send",
+            "yes",
+            "--",
+            "set_place_vesting_config",
+            "--place_id",
+            "7",
+            "--config",
+            r#"{"cliff_seconds":"60","duration_seconds":"3600"}"#,
+            "--authorized",
+        ]
+    );
+}
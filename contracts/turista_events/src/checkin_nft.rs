@@ -0,0 +1,191 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, String, Symbol};
+
+use crate::ContractEvent;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CheckinInitializedEvent {
+    pub admin: Address,
+    pub tur_token_contract: Address,
+}
+
+impl ContractEvent for CheckinInitializedEvent {
+    const NAME: Symbol = symbol_short!("init");
+}
+
+// Check-in NFT minted to a visitor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CheckinEvent {
+    pub token_id: u64,
+    pub owner: Address,
+    pub place_id: u32,
+    pub place_name: String,
+    pub timestamp: u64,
+}
+
+impl ContractEvent for CheckinEvent {
+    const NAME: Symbol = symbol_short!("checkin");
+}
+
+// TUR reward minted to the visitor at check-in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardPaidEvent {
+    pub user: Address,
+    pub token_id: u64,
+    pub amount: i128,
+}
+
+impl ContractEvent for RewardPaidEvent {
+    const NAME: Symbol = symbol_short!("rwd_paid");
+}
+
+// Reward that could not be minted at check-in, added to the pending ledger
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardPendingEvent {
+    pub user: Address,
+    pub token_id: u64,
+    pub amount: i128,
+    pub total_pending: i128,
+}
+
+impl ContractEvent for RewardPendingEvent {
+    const NAME: Symbol = symbol_short!("rwd_pend");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRewardClaimedEvent {
+    pub user: Address,
+    pub amount: i128,
+}
+
+impl ContractEvent for PendingRewardClaimedEvent {
+    const NAME: Symbol = symbol_short!("rwd_clm");
+}
+
+// Vesting terms set globally (`place_id` is None) or for one place.
+// `enabled` is false when the configuration was removed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingConfigUpdatedEvent {
+    pub place_id: Option<u32>,
+    pub enabled: bool,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+}
+
+impl ContractEvent for VestingConfigUpdatedEvent {
+    const NAME: Symbol = symbol_short!("vest_cfg");
+}
+
+// Check-in reward granted as a vesting position instead of paid out
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingGrantedEvent {
+    pub user: Address,
+    pub token_id: u64,
+    pub amount: i128,
+    pub start_timestamp: u64,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+}
+
+impl ContractEvent for VestingGrantedEvent {
+    const NAME: Symbol = symbol_short!("vest_new");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingClaimedEvent {
+    pub user: Address,
+    pub amount: i128,
+}
+
+impl ContractEvent for VestingClaimedEvent {
+    const NAME: Symbol = symbol_short!("vest_clm");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PhotoAttachedEvent {
+    pub token_id: u64,
+    pub owner: Address,
+    pub content_hash: BytesN<32>,
+    pub uri: Option<String>,
+}
+
+impl ContractEvent for PhotoAttachedEvent {
+    const NAME: Symbol = symbol_short!("photo");
+}
+
+// Review posted, or edited when `edited` is true
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewPostedEvent {
+    pub token_id: u64,
+    pub place_id: u32,
+    pub reviewer: Address,
+    pub rating: u32,
+    pub content_hash: Bytes,
+    pub edited: bool,
+}
+
+impl ContractEvent for ReviewPostedEvent {
+    const NAME: Symbol = symbol_short!("review");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewModeratedEvent {
+    pub token_id: u64,
+    pub place_id: u32,
+    pub hidden: bool,
+}
+
+impl ContractEvent for ReviewModeratedEvent {
+    const NAME: Symbol = symbol_short!("rev_mod");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlaceLevelsUpdatedEvent {
+    pub place_id: u32,
+    pub tier_count: u32,
+}
+
+impl ContractEvent for PlaceLevelsUpdatedEvent {
+    const NAME: Symbol = symbol_short!("lvl_cfg");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VisitRecordedEvent {
+    pub token_id: u64,
+    pub user: Address,
+    pub place_id: u32,
+    pub visit_count: u32,
+    pub timestamp: u64,
+}
+
+impl ContractEvent for VisitRecordedEvent {
+    const NAME: Symbol = symbol_short!("visit");
+}
+
+// Stored level of an NFT changed; `image_url` is the new level's artwork
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LevelChangedEvent {
+    pub token_id: u64,
+    pub owner: Address,
+    pub place_id: u32,
+    pub previous_level: u32,
+    pub level: u32,
+    pub image_url: String,
+}
+
+impl ContractEvent for LevelChangedEvent {
+    const NAME: Symbol = symbol_short!("level");
+}
@@ -0,0 +1,124 @@
+#![no_std]
+
+//! Event schema shared by the Turista contracts.
+//!
+//! Every state change is published with the topics `(name, SCHEMA_VERSION)`
+//! and one of the `#[contracttype]` structs of this crate as data, so an
+//! indexer can pick the decoder from the topics alone:
+//!
+//! | Contract    | Name       | Data                            |
+//! |-------------|------------|---------------------------------|
+//! | TurToken    | `init`     | [`TokenInitializedEvent`]       |
+//! | TurToken    | `minter`   | [`MinterUpdatedEvent`]          |
+//! | TurToken    | `mint_lim` | [`MinterLimitsUpdatedEvent`]    |
+//! | TurToken    | `migrate`  | [`BalancesMigratedEvent`]       |
+//! | TurToken    | `upgrade`  | [`ContractUpgradedEvent`]       |
+//! | TurToken    | `role`     | [`RoleUpdatedEvent`]            |
+//! | TurToken    | `paused`   | [`PausedEvent`]                 |
+//! | TurToken    | `frozen`   | [`AccountFrozenEvent`]          |
+//! | TurToken    | `clawback` | [`ClawbackEvent`]               |
+//! | TurToken    | `treasury` | [`TreasuryUpdatedEvent`]        |
+//! | TurToken    | `lifetime` | [`PointsLifetimeUpdatedEvent`]  |
+//! | TurToken    | `expired`  | [`PointsExpiredEvent`]          |
+//! | TurToken    | `exempt`   | [`ExpiryExemptionUpdatedEvent`] |
+//! | TurToken    | `policy`   | [`TransferPolicyUpdatedEvent`]  |
+//! | TurToken    | `allowlst` | [`AllowlistUpdatedEvent`]       |
+//! | TurToken    | `delegate` | [`DelegateChangedEvent`]        |
+//! | CheckinNFT  | `init`     | [`CheckinInitializedEvent`]     |
+//! | CheckinNFT  | `checkin`  | [`CheckinEvent`]                |
+//! | CheckinNFT  | `rwd_paid` | [`RewardPaidEvent`]             |
+//! | CheckinNFT  | `rwd_pend` | [`RewardPendingEvent`]          |
+//! | CheckinNFT  | `rwd_clm`  | [`PendingRewardClaimedEvent`]   |
+//! | CheckinNFT  | `vest_cfg` | [`VestingConfigUpdatedEvent`]   |
+//! | CheckinNFT  | `vest_new` | [`VestingGrantedEvent`]         |
+//! | CheckinNFT  | `vest_clm` | [`VestingClaimedEvent`]         |
+//! | CheckinNFT  | `photo`    | [`PhotoAttachedEvent`]          |
+//! | CheckinNFT  | `review`   | [`ReviewPostedEvent`]           |
+//! | CheckinNFT  | `rev_mod`  | [`ReviewModeratedEvent`]        |
+//! | CheckinNFT  | `lvl_cfg`  | [`PlaceLevelsUpdatedEvent`]     |
+//! | CheckinNFT  | `visit`    | [`VisitRecordedEvent`]          |
+//! | CheckinNFT  | `level`    | [`LevelChangedEvent`]           |
+//! | Marketplace | `init`     | [`MarketplaceInitializedEvent`] |
+//! | Marketplace | `purchase` | [`PurchaseEvent`]               |
+//! | Marketplace | `merchant` | [`MerchantUpdatedEvent`]        |
+//! | Marketplace | `fee`      | [`PlatformFeeUpdatedEvent`]     |
+//! | Staking     | `init`     | [`StakingInitializedEvent`]     |
+//! | Staking     | `stake`    | [`StakedEvent`]                 |
+//! | Staking     | `unstake`  | [`UnstakedEvent`]               |
+//! | Staking     | `withdraw` | [`StakeWithdrawnEvent`]         |
+//! | Staking     | `tier_cfg` | [`TierConfigUpdatedEvent`]      |
+//! | Staking     | `cooldown` | [`CooldownUpdatedEvent`]        |
+//!
+//! TurToken balance changes follow the standard token (SEP-41) format
+//! instead, so wallets and explorers understand them. `transfer` carries
+//! `(name, from, to)` topics, `mint` `(name, minter, to)` and `burn`
+//! `(name, from)`, all with the amount as data; `approve` carries
+//! `(name, from, spender)` with `(amount, expiration_ledger)` as data.
+//!
+//! Names are only unique per contract, so events must be matched together
+//! with the id of the contract that emitted them. Adding a field or changing
+//! a field type bumps [`SCHEMA_VERSION`].
+
+use soroban_sdk::{Env, IntoVal, Symbol, Val};
+
+mod checkin_nft;
+mod marketplace;
+mod staking;
+mod tur_token;
+
+pub use checkin_nft::*;
+pub use marketplace::*;
+pub use staking::*;
+pub use tur_token::*;
+
+/// Version of the event schema, published as the second topic
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Typed event published with the `(NAME, SCHEMA_VERSION)` topics
+pub trait ContractEvent: IntoVal<Env, Val> + Sized {
+    const NAME: Symbol;
+
+    fn publish(self, env: &Env) {
+        env.events().publish((Self::NAME, SCHEMA_VERSION), self);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        contract,
+        testutils::{Address as _, Events},
+        vec, Address, TryFromVal,
+    };
+
+    #[contract]
+    struct Emitter;
+
+    #[test]
+    fn test_publish() {
+        let env = Env::default();
+        let contract_id = env.register(Emitter, ());
+
+        let event = MinterUpdatedEvent {
+            minter: Address::generate(&env),
+            authorized: true,
+        };
+        env.as_contract(&contract_id, || event.clone().publish(&env));
+
+        let (emitter, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(emitter, contract_id);
+        assert_eq!(
+            topics,
+            vec![
+                &env,
+                MinterUpdatedEvent::NAME.into_val(&env),
+                SCHEMA_VERSION.into_val(&env)
+            ]
+        );
+        assert_eq!(
+            MinterUpdatedEvent::try_from_val(&env, &data).unwrap(),
+            event
+        );
+    }
+}
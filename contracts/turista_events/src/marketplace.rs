@@ -0,0 +1,55 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Symbol};
+
+use crate::ContractEvent;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketplaceInitializedEvent {
+    pub admin: Address,
+    pub platform_address: Address,
+    pub tur_token_contract: Address,
+    pub xlm_token_contract: Address,
+}
+
+impl ContractEvent for MarketplaceInitializedEvent {
+    const NAME: Symbol = symbol_short!("init");
+}
+
+// Purchase paid in XLM, optionally discounted by burning TUR
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurchaseEvent {
+    pub buyer: Address,
+    pub merchant: Address,
+    pub total_xlm: i128,
+    pub merchant_xlm: i128,
+    pub platform_fee: i128,
+    pub amount_tur_burned: i128,
+    pub timestamp: u64,
+}
+
+impl ContractEvent for PurchaseEvent {
+    const NAME: Symbol = symbol_short!("purchase");
+}
+
+// Merchant registered (`approved`) or removed by the admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerchantUpdatedEvent {
+    pub merchant: Address,
+    pub approved: bool,
+}
+
+impl ContractEvent for MerchantUpdatedEvent {
+    const NAME: Symbol = symbol_short!("merchant");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFeeUpdatedEvent {
+    pub fee_bps: u32,
+}
+
+impl ContractEvent for PlatformFeeUpdatedEvent {
+    const NAME: Symbol = symbol_short!("fee");
+}
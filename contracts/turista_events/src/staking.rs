@@ -0,0 +1,75 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Symbol};
+
+use crate::ContractEvent;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakingInitializedEvent {
+    pub admin: Address,
+    pub tur_token_contract: Address,
+    pub cooldown_seconds: u64,
+}
+
+impl ContractEvent for StakingInitializedEvent {
+    const NAME: Symbol = symbol_short!("init");
+}
+
+// TUR locked by a user; `staked` is the user's stake afterwards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakedEvent {
+    pub user: Address,
+    pub amount: i128,
+    pub staked: i128,
+}
+
+impl ContractEvent for StakedEvent {
+    const NAME: Symbol = symbol_short!("stake");
+}
+
+// Stake moved to unbonding, withdrawable from `release_at`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnstakedEvent {
+    pub user: Address,
+    pub amount: i128,
+    pub release_at: u64,
+}
+
+impl ContractEvent for UnstakedEvent {
+    const NAME: Symbol = symbol_short!("unstake");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeWithdrawnEvent {
+    pub user: Address,
+    pub amount: i128,
+}
+
+impl ContractEvent for StakeWithdrawnEvent {
+    const NAME: Symbol = symbol_short!("withdraw");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierConfigUpdatedEvent {
+    pub silver_min_stake: i128,
+    pub silver_min_seconds: u64,
+    pub gold_min_stake: i128,
+    pub gold_min_seconds: u64,
+}
+
+impl ContractEvent for TierConfigUpdatedEvent {
+    const NAME: Symbol = symbol_short!("tier_cfg");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CooldownUpdatedEvent {
+    pub cooldown_seconds: u64,
+}
+
+impl ContractEvent for CooldownUpdatedEvent {
+    const NAME: Symbol = symbol_short!("cooldown");
+}
@@ -0,0 +1,191 @@
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, String, Symbol};
+
+use crate::ContractEvent;
+
+// Token created and initial supply minted to the admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenInitializedEvent {
+    pub admin: Address,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    pub initial_supply: i128,
+}
+
+impl ContractEvent for TokenInitializedEvent {
+    const NAME: Symbol = symbol_short!("init");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterUpdatedEvent {
+    pub minter: Address,
+    pub authorized: bool,
+}
+
+impl ContractEvent for MinterUpdatedEvent {
+    const NAME: Symbol = symbol_short!("minter");
+}
+
+// Balances moved out of instance storage by `migrate_balances`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalancesMigratedEvent {
+    pub count: u32,
+}
+
+impl ContractEvent for BalancesMigratedEvent {
+    const NAME: Symbol = symbol_short!("migrate");
+}
+
+// Contract code replaced by `upgrade`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractUpgradedEvent {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+impl ContractEvent for ContractUpgradedEvent {
+    const NAME: Symbol = symbol_short!("upgrade");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterLimitsUpdatedEvent {
+    pub minter: Address,
+    pub quota: i128,
+    pub rate_limit: i128,
+    pub period_seconds: u64,
+}
+
+impl ContractEvent for MinterLimitsUpdatedEvent {
+    const NAME: Symbol = symbol_short!("mint_lim");
+}
+
+// `role` is the `Role` discriminant of the token contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleUpdatedEvent {
+    pub role: u32,
+    pub account: Address,
+    pub granted: bool,
+}
+
+impl ContractEvent for RoleUpdatedEvent {
+    const NAME: Symbol = symbol_short!("role");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PausedEvent {
+    pub caller: Address,
+    pub paused: bool,
+}
+
+impl ContractEvent for PausedEvent {
+    const NAME: Symbol = symbol_short!("paused");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountFrozenEvent {
+    pub account: Address,
+    pub frozen: bool,
+}
+
+impl ContractEvent for AccountFrozenEvent {
+    const NAME: Symbol = symbol_short!("frozen");
+}
+
+// Tokens moved from an account to the treasury by a compliance officer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClawbackEvent {
+    pub caller: Address,
+    pub from: Address,
+    pub treasury: Address,
+    pub amount: i128,
+}
+
+impl ContractEvent for ClawbackEvent {
+    const NAME: Symbol = symbol_short!("clawback");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryUpdatedEvent {
+    pub treasury: Address,
+}
+
+impl ContractEvent for TreasuryUpdatedEvent {
+    const NAME: Symbol = symbol_short!("treasury");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsLifetimeUpdatedEvent {
+    pub lifetime_seconds: u64,
+}
+
+impl ContractEvent for PointsLifetimeUpdatedEvent {
+    const NAME: Symbol = symbol_short!("lifetime");
+}
+
+// Expired points of an account burned by `expire`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsExpiredEvent {
+    pub account: Address,
+    pub amount: i128,
+}
+
+impl ContractEvent for PointsExpiredEvent {
+    const NAME: Symbol = symbol_short!("expired");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryExemptionUpdatedEvent {
+    pub account: Address,
+    pub exempt: bool,
+}
+
+impl ContractEvent for ExpiryExemptionUpdatedEvent {
+    const NAME: Symbol = symbol_short!("exempt");
+}
+
+// `policy` is the `TransferPolicy` discriminant of the token contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferPolicyUpdatedEvent {
+    pub policy: u32,
+}
+
+impl ContractEvent for TransferPolicyUpdatedEvent {
+    const NAME: Symbol = symbol_short!("policy");
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowlistUpdatedEvent {
+    pub account: Address,
+    pub allowed: bool,
+}
+
+impl ContractEvent for AllowlistUpdatedEvent {
+    const NAME: Symbol = symbol_short!("allowlst");
+}
+
+// `to_delegate` is the delegator itself when it takes its weight back
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegateChangedEvent {
+    pub delegator: Address,
+    pub from_delegate: Option<Address>,
+    pub to_delegate: Address,
+}
+
+impl ContractEvent for DelegateChangedEvent {
+    const NAME: Symbol = symbol_short!("delegate");
+}
@@ -0,0 +1,373 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use crate::{Decoder, Error, Event, RawEvent};
+
+// Amounts are i128 on chain, wider than SQLite integers, so they are
+// stored as decimal text and added up in Rust
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY,
+    ledger INTEGER NOT NULL,
+    ledger_closed_at TEXT NOT NULL,
+    contract_id TEXT NOT NULL,
+    name TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS checkins (
+    token_id INTEGER PRIMARY KEY,
+    owner TEXT NOT NULL,
+    place_id INTEGER NOT NULL,
+    place_name TEXT NOT NULL,
+    timestamp INTEGER NOT NULL,
+    event_id TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS checkins_owner ON checkins (owner);
+CREATE TABLE IF NOT EXISTS rewards (
+    event_id TEXT PRIMARY KEY,
+    user TEXT NOT NULL,
+    token_id INTEGER,
+    kind TEXT NOT NULL,
+    amount TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS rewards_user ON rewards (user);
+CREATE TABLE IF NOT EXISTS balance_changes (
+    event_id TEXT NOT NULL,
+    account TEXT NOT NULL,
+    delta TEXT NOT NULL,
+    PRIMARY KEY (event_id, account)
+);
+CREATE TABLE IF NOT EXISTS purchases (
+    event_id TEXT PRIMARY KEY,
+    buyer TEXT NOT NULL,
+    merchant TEXT NOT NULL,
+    total_xlm TEXT NOT NULL,
+    merchant_xlm TEXT NOT NULL,
+    platform_fee TEXT NOT NULL,
+    amount_tur_burned TEXT NOT NULL,
+    timestamp INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS purchases_merchant ON purchases (merchant);
+";
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Checkin {
+    pub token_id: u64,
+    pub owner: String,
+    pub place_id: u32,
+    pub place_name: String,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Reward {
+    pub event_id: String,
+    pub token_id: Option<u64>,
+    pub kind: String,
+    pub amount: i128,
+}
+
+/// TUR balance of an account right after one of its balance changes
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct BalancePoint {
+    pub event_id: String,
+    pub ledger: u32,
+    pub delta: i128,
+    pub balance: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Purchase {
+    pub event_id: String,
+    pub buyer: String,
+    pub total_xlm: i128,
+    pub merchant_xlm: i128,
+    pub platform_fee: i128,
+    pub amount_tur_burned: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct MerchantSales {
+    pub purchases: Vec<Purchase>,
+    pub total_xlm: i128,
+    pub merchant_xlm: i128,
+    pub platform_fee: i128,
+    pub amount_tur_burned: i128,
+}
+
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, Error> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Database { conn })
+    }
+
+    /// Store a batch of events, skipping those already ingested, and
+    /// return the number of new events. The batch is stored atomically.
+    pub fn ingest(&mut self, decoder: &Decoder, events: &[RawEvent]) -> Result<usize, Error> {
+        let tx = self.conn.transaction()?;
+        let mut ingested = 0;
+
+        for raw in events {
+            let Some(name) = decoder.name(raw) else {
+                continue;
+            };
+            let event = decoder.decode(raw)?;
+
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO events (id, ledger, ledger_closed_at, contract_id, name)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    raw.id,
+                    raw.ledger,
+                    raw.ledger_closed_at,
+                    raw.contract_id,
+                    name
+                ],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            ingested += 1;
+
+            let Some(event) = event else {
+                continue;
+            };
+            match event {
+                Event::Checkin {
+                    token_id,
+                    owner,
+                    place_id,
+                    place_name,
+                    timestamp,
+                } => {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO checkins
+                         (token_id, owner, place_id, place_name, timestamp, event_id)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![token_id, owner, place_id, place_name, timestamp, raw.id],
+                    )?;
+                }
+                Event::Reward {
+                    user,
+                    token_id,
+                    kind,
+                    amount,
+                } => {
+                    tx.execute(
+                        "INSERT INTO rewards (event_id, user, token_id, kind, amount)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![raw.id, user, token_id, kind.as_str(), amount.to_string()],
+                    )?;
+                }
+                Event::Transfer { from, to, amount } => {
+                    // A transfer to oneself leaves the balance unchanged
+                    if from != to {
+                        insert_balance_change(&tx, &raw.id, &from, -amount)?;
+                        insert_balance_change(&tx, &raw.id, &to, amount)?;
+                    }
+                }
+                Event::Mint { to, amount } => {
+                    insert_balance_change(&tx, &raw.id, &to, amount)?;
+                }
+                Event::Burn { from, amount } => {
+                    insert_balance_change(&tx, &raw.id, &from, -amount)?;
+                }
+                Event::Purchase {
+                    buyer,
+                    merchant,
+                    total_xlm,
+                    merchant_xlm,
+                    platform_fee,
+                    amount_tur_burned,
+                    timestamp,
+                } => {
+                    tx.execute(
+                        "INSERT INTO purchases
+                         (event_id, buyer, merchant, total_xlm, merchant_xlm, platform_fee,
+                          amount_tur_burned, timestamp)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![
+                            raw.id,
+                            buyer,
+                            merchant,
+                            total_xlm.to_string(),
+                            merchant_xlm.to_string(),
+                            platform_fee.to_string(),
+                            amount_tur_burned.to_string(),
+                            timestamp
+                        ],
+                    )?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(ingested)
+    }
+
+    /// Ledger of the latest ingested event, to resume polling from
+    pub fn latest_ledger(&self) -> Result<Option<u32>, Error> {
+        let ledger = self
+            .conn
+            .query_row("SELECT MAX(ledger) FROM events", [], |row| row.get(0))
+            .optional()?
+            .flatten();
+        Ok(ledger)
+    }
+
+    /// Check-in NFTs owned by `owner`, oldest first
+    pub fn user_collection(&self, owner: &str) -> Result<Vec<Checkin>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT token_id, owner, place_id, place_name, timestamp
+             FROM checkins WHERE owner = ?1 ORDER BY token_id",
+        )?;
+        let checkins = stmt
+            .query_map([owner], |row| {
+                Ok(Checkin {
+                    token_id: row.get(0)?,
+                    owner: row.get(1)?,
+                    place_id: row.get(2)?,
+                    place_name: row.get(3)?,
+                    timestamp: row.get(4)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(checkins)
+    }
+
+    /// Check-in rewards of `user`, in event order
+    pub fn user_rewards(&self, user: &str) -> Result<Vec<Reward>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT event_id, token_id, kind, amount
+             FROM rewards WHERE user = ?1 ORDER BY event_id",
+        )?;
+        let rows = stmt
+            .query_map([user], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<(String, Option<u64>, String, String)>, _>>()?;
+
+        rows.into_iter()
+            .map(|(event_id, token_id, kind, amount)| {
+                Ok(Reward {
+                    amount: parse_amount(&event_id, &amount)?,
+                    event_id,
+                    token_id,
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    /// TUR balance of `account` after each of its indexed balance changes
+    pub fn balance_history(&self, account: &str) -> Result<Vec<BalancePoint>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.event_id, e.ledger, c.delta
+             FROM balance_changes c JOIN events e ON e.id = c.event_id
+             WHERE c.account = ?1 ORDER BY e.ledger, c.event_id",
+        )?;
+        let rows = stmt
+            .query_map([account], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<(String, u32, String)>, _>>()?;
+
+        let mut balance = 0;
+        rows.into_iter()
+            .map(|(event_id, ledger, delta)| {
+                let delta = parse_amount(&event_id, &delta)?;
+                balance += delta;
+                Ok(BalancePoint {
+                    event_id,
+                    ledger,
+                    delta,
+                    balance,
+                })
+            })
+            .collect()
+    }
+
+    /// Purchases paid to `merchant` and their totals
+    pub fn merchant_sales(&self, merchant: &str) -> Result<MerchantSales, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT event_id, buyer, total_xlm, merchant_xlm, platform_fee,
+                    amount_tur_burned, timestamp
+             FROM purchases WHERE merchant = ?1 ORDER BY event_id",
+        )?;
+        let rows = stmt
+            .query_map([merchant], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    [
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                        row.get::<_, String>(5)?,
+                    ],
+                    row.get::<_, u64>(6)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut sales = MerchantSales::default();
+        for (event_id, buyer, amounts, timestamp) in rows {
+            let [total_xlm, merchant_xlm, platform_fee, amount_tur_burned] =
+                amounts.map(|amount| parse_amount(&event_id, &amount));
+            let purchase = Purchase {
+                total_xlm: total_xlm?,
+                merchant_xlm: merchant_xlm?,
+                platform_fee: platform_fee?,
+                amount_tur_burned: amount_tur_burned?,
+                event_id,
+                buyer,
+                timestamp,
+            };
+
+            sales.total_xlm += purchase.total_xlm;
+            sales.merchant_xlm += purchase.merchant_xlm;
+            sales.platform_fee += purchase.platform_fee;
+            sales.amount_tur_burned += purchase.amount_tur_burned;
+            sales.purchases.push(purchase);
+        }
+
+        Ok(sales)
+    }
+}
+
+fn insert_balance_change(
+    conn: &Connection,
+    event_id: &str,
+    account: &str,
+    delta: i128,
+) -> Result<(), Error> {
+    conn.execute(
+        "INSERT INTO balance_changes (event_id, account, delta) VALUES (?1, ?2, ?3)",
+        params![event_id, account, delta.to_string()],
+    )?;
+    Ok(())
+}
+
+fn parse_amount(event_id: &str, amount: &str) -> Result<i128, Error> {
+    amount.parse().map_err(|_| Error::Decode {
+        event_id: event_id.to_string(),
+        reason: format!("stored amount `{amount}` is not a number"),
+    })
+}
@@ -0,0 +1,282 @@
+use soroban_sdk::{
+    testutils::EnvTestConfig,
+    xdr::{ScAddress, ScVal},
+    Address, Env, Symbol, TryFromVal, Val,
+};
+use turista_events::{
+    CheckinEvent, ContractEvent, PendingRewardClaimedEvent, PurchaseEvent, RewardPaidEvent,
+    RewardPendingEvent, TokenInitializedEvent, VestingClaimedEvent, VestingGrantedEvent,
+    SCHEMA_VERSION,
+};
+
+use crate::{Error, RawEvent};
+
+/// Ids of the deployed contracts, as strkeys (`C...`)
+#[derive(Clone, Debug)]
+pub struct Contracts {
+    pub tur_token: String,
+    pub checkin_nft: String,
+    pub marketplace: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RewardKind {
+    Paid,
+    Pending,
+    PendingClaimed,
+    Vested,
+    VestingClaimed,
+}
+
+impl RewardKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RewardKind::Paid => "paid",
+            RewardKind::Pending => "pending",
+            RewardKind::PendingClaimed => "pending_claimed",
+            RewardKind::Vested => "vested",
+            RewardKind::VestingClaimed => "vesting_claimed",
+        }
+    }
+}
+
+/// Indexed events, with addresses as strkeys
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    Checkin {
+        token_id: u64,
+        owner: String,
+        place_id: u32,
+        place_name: String,
+        timestamp: u64,
+    },
+    Reward {
+        user: String,
+        token_id: Option<u64>,
+        kind: RewardKind,
+        amount: i128,
+    },
+    Transfer {
+        from: String,
+        to: String,
+        amount: i128,
+    },
+    // Initial supply and minted tokens
+    Mint {
+        to: String,
+        amount: i128,
+    },
+    Burn {
+        from: String,
+        amount: i128,
+    },
+    Purchase {
+        buyer: String,
+        merchant: String,
+        total_xlm: i128,
+        merchant_xlm: i128,
+        platform_fee: i128,
+        amount_tur_burned: i128,
+        timestamp: u64,
+    },
+}
+
+/// Decodes event payloads with the schema crate types
+pub struct Decoder {
+    env: Env,
+    contracts: Contracts,
+}
+
+impl Decoder {
+    pub fn new(contracts: Contracts) -> Self {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        Decoder { env, contracts }
+    }
+
+    /// Event name, when the event comes from one of our contracts and uses
+    /// the current schema version or, for TurToken, the standard token format
+    pub fn name(&self, raw: &RawEvent) -> Option<String> {
+        if raw.contract_id != self.contracts.tur_token
+            && raw.contract_id != self.contracts.checkin_nft
+            && raw.contract_id != self.contracts.marketplace
+        {
+            return None;
+        }
+
+        match raw.topics.as_slice() {
+            [ScVal::Symbol(name), ScVal::U32(SCHEMA_VERSION)] => Some(name.to_utf8_string_lossy()),
+            [ScVal::Symbol(name), ScVal::Address(_), ..]
+                if raw.contract_id == self.contracts.tur_token =>
+            {
+                Some(name.to_utf8_string_lossy())
+            }
+            _ => None,
+        }
+    }
+
+    /// Decode an event, returning None for events that are not indexed
+    pub fn decode(&self, raw: &RawEvent) -> Result<Option<Event>, Error> {
+        let Some(name) = self.name(raw) else {
+            return Ok(None);
+        };
+
+        let event = if raw.contract_id == self.contracts.tur_token {
+            if raw.topics.get(1) == Some(&ScVal::U32(SCHEMA_VERSION)) {
+                self.decode_token_schema_event(raw, &name)?
+            } else {
+                decode_standard_token_event(raw, &name)?
+            }
+        } else if raw.contract_id == self.contracts.checkin_nft {
+            let name = Symbol::new(&self.env, &name);
+            if name == CheckinEvent::NAME {
+                let event: CheckinEvent = self.payload(raw)?;
+                Some(Event::Checkin {
+                    token_id: event.token_id,
+                    owner: strkey(&event.owner),
+                    place_id: event.place_id,
+                    place_name: text(&event.place_name),
+                    timestamp: event.timestamp,
+                })
+            } else if name == RewardPaidEvent::NAME {
+                let event: RewardPaidEvent = self.payload(raw)?;
+                Some(reward(
+                    &event.user,
+                    Some(event.token_id),
+                    RewardKind::Paid,
+                    event.amount,
+                ))
+            } else if name == RewardPendingEvent::NAME {
+                let event: RewardPendingEvent = self.payload(raw)?;
+                Some(reward(
+                    &event.user,
+                    Some(event.token_id),
+                    RewardKind::Pending,
+                    event.amount,
+                ))
+            } else if name == PendingRewardClaimedEvent::NAME {
+                let event: PendingRewardClaimedEvent = self.payload(raw)?;
+                Some(reward(
+                    &event.user,
+                    None,
+                    RewardKind::PendingClaimed,
+                    event.amount,
+                ))
+            } else if name == VestingGrantedEvent::NAME {
+                let event: VestingGrantedEvent = self.payload(raw)?;
+                Some(reward(
+                    &event.user,
+                    Some(event.token_id),
+                    RewardKind::Vested,
+                    event.amount,
+                ))
+            } else if name == VestingClaimedEvent::NAME {
+                let event: VestingClaimedEvent = self.payload(raw)?;
+                Some(reward(
+                    &event.user,
+                    None,
+                    RewardKind::VestingClaimed,
+                    event.amount,
+                ))
+            } else {
+                None
+            }
+        } else if Symbol::new(&self.env, &name) == PurchaseEvent::NAME {
+            let event: PurchaseEvent = self.payload(raw)?;
+            Some(Event::Purchase {
+                buyer: strkey(&event.buyer),
+                merchant: strkey(&event.merchant),
+                total_xlm: event.total_xlm,
+                merchant_xlm: event.merchant_xlm,
+                platform_fee: event.platform_fee,
+                amount_tur_burned: event.amount_tur_burned,
+                timestamp: event.timestamp,
+            })
+        } else {
+            None
+        };
+
+        Ok(event)
+    }
+
+    fn decode_token_schema_event(
+        &self,
+        raw: &RawEvent,
+        name: &str,
+    ) -> Result<Option<Event>, Error> {
+        if Symbol::new(&self.env, name) != TokenInitializedEvent::NAME {
+            return Ok(None);
+        }
+
+        let event: TokenInitializedEvent = self.payload(raw)?;
+        Ok(Some(Event::Mint {
+            to: strkey(&event.admin),
+            amount: event.initial_supply,
+        }))
+    }
+
+    fn payload<T: TryFromVal<Env, Val>>(&self, raw: &RawEvent) -> Result<T, Error> {
+        let decode_error = |reason: &str| Error::Decode {
+            event_id: raw.id.clone(),
+            reason: reason.to_string(),
+        };
+
+        let val = Val::try_from_val(&self.env, &raw.value)
+            .map_err(|_| decode_error("payload is not a valid value"))?;
+        T::try_from_val(&self.env, &val)
+            .map_err(|_| decode_error("payload does not match the schema"))
+    }
+}
+
+fn reward(user: &Address, token_id: Option<u64>, kind: RewardKind, amount: i128) -> Event {
+    Event::Reward {
+        user: strkey(user),
+        token_id,
+        kind,
+        amount,
+    }
+}
+
+fn strkey(address: &Address) -> String {
+    ScAddress::from(address).to_string()
+}
+
+fn text(string: &soroban_sdk::String) -> String {
+    let mut buf = vec![0; string.len() as usize];
+    string.copy_into_slice(&mut buf);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+// Balance changes published in the standard token format
+fn decode_standard_token_event(raw: &RawEvent, name: &str) -> Result<Option<Event>, Error> {
+    let decode_error = |reason: &str| Error::Decode {
+        event_id: raw.id.clone(),
+        reason: reason.to_string(),
+    };
+    let address = |index: usize| match raw.topics.get(index) {
+        Some(ScVal::Address(address)) => Ok(address.to_string()),
+        _ => Err(decode_error("missing address topic")),
+    };
+    let amount =
+        || i128::try_from(raw.value.clone()).map_err(|_| decode_error("amount is not an i128"));
+
+    let event = match name {
+        "transfer" => Event::Transfer {
+            from: address(1)?,
+            to: address(2)?,
+            amount: amount()?,
+        },
+        "mint" => Event::Mint {
+            to: address(2)?,
+            amount: amount()?,
+        },
+        "burn" => Event::Burn {
+            from: address(1)?,
+            amount: amount()?,
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(event))
+}
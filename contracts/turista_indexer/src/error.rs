@@ -0,0 +1,54 @@
+use core::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Xdr(soroban_sdk::xdr::Error),
+    Sqlite(rusqlite::Error),
+    // Event dump that does not follow the RPC `getEvents` layout
+    InvalidDump(String),
+    // Event whose payload does not match its schema
+    Decode { event_id: String, reason: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "io error: {err}"),
+            Error::Json(err) => write!(f, "invalid json: {err}"),
+            Error::Xdr(err) => write!(f, "invalid xdr: {err}"),
+            Error::Sqlite(err) => write!(f, "database error: {err}"),
+            Error::InvalidDump(reason) => write!(f, "invalid event dump: {reason}"),
+            Error::Decode { event_id, reason } => {
+                write!(f, "cannot decode event {event_id}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+impl From<soroban_sdk::xdr::Error> for Error {
+    fn from(err: soroban_sdk::xdr::Error) -> Self {
+        Error::Xdr(err)
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Error::Sqlite(err)
+    }
+}
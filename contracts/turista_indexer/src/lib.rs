@@ -0,0 +1,17 @@
+//! Off-chain indexer for the Turista contracts.
+//!
+//! Contract events are read from a JSON dump of RPC `getEvents` results (or
+//! captured from a soroban test environment), decoded with the
+//! [`turista_events`] schema and stored in SQLite, where check-ins, rewards,
+//! TUR balance changes and marketplace purchases can be queried without
+//! going back to RPC.
+
+mod db;
+mod decode;
+mod error;
+mod source;
+
+pub use db::{BalancePoint, Checkin, Database, MerchantSales, Purchase, Reward};
+pub use decode::{Contracts, Decoder, Event, RewardKind};
+pub use error::Error;
+pub use source::{capture, parse_dump, RawEvent};
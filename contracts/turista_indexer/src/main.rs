@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use turista_indexer::{parse_dump, Contracts, Database, Decoder, Error};
+
+/// Index Turista contract events into a local SQLite database
+#[derive(Parser)]
+#[command(name = "turista-indexer")]
+struct Cli {
+    /// SQLite database file, created when missing
+    #[arg(long, default_value = "turista.db")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Ingest a JSON dump of RPC `getEvents` results
+    Ingest {
+        /// TurToken contract id
+        #[arg(long, env = "VITE_TUR_TOKEN_CONTRACT")]
+        tur_token: String,
+        /// CheckinNFT contract id
+        #[arg(long, env = "VITE_CHECKIN_NFT_CONTRACT")]
+        checkin_nft: String,
+        /// Marketplace contract id
+        #[arg(long, env = "VITE_MARKETPLACE_CONTRACT")]
+        marketplace: String,
+        /// Event dump files, ingested in order
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Ledger to resume polling from
+    LatestLedger,
+    /// Check-in NFTs owned by an address
+    Collection { owner: String },
+    /// Check-in rewards of an address
+    Rewards { user: String },
+    /// TUR balance of an address after each change
+    Balances { account: String },
+    /// Purchases paid to a merchant, with totals
+    Sales { merchant: String },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
+    let mut db = Database::open(&cli.db)?;
+
+    match cli.command {
+        Command::Ingest {
+            tur_token,
+            checkin_nft,
+            marketplace,
+            files,
+        } => {
+            let decoder = Decoder::new(Contracts {
+                tur_token,
+                checkin_nft,
+                marketplace,
+            });
+            for file in files {
+                let events = parse_dump(&std::fs::read_to_string(&file)?)?;
+                let ingested = db.ingest(&decoder, &events)?;
+                println!("{}: {ingested} new events", file.display());
+            }
+            Ok(())
+        }
+        Command::LatestLedger => print(&db.latest_ledger()?),
+        Command::Collection { owner } => print(&db.user_collection(&owner)?),
+        Command::Rewards { user } => print(&db.user_rewards(&user)?),
+        Command::Balances { account } => print(&db.balance_history(&account)?),
+        Command::Sales { merchant } => print(&db.merchant_sales(&merchant)?),
+    }
+}
+
+fn print(value: &impl Serialize) -> Result<(), Error> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use serde::Deserialize;
+use soroban_sdk::{
+    testutils::Events,
+    xdr::{Limits, ReadXdr, ScAddress, ScVal},
+    Env, TryFromVal,
+};
+
+use crate::Error;
+
+/// Contract event as returned by RPC `getEvents`, with undecoded payloads
+#[derive(Clone, Debug, PartialEq)]
+pub struct RawEvent {
+    pub id: String,
+    pub ledger: u32,
+    pub ledger_closed_at: String,
+    pub contract_id: String,
+    pub topics: Vec<ScVal>,
+    pub value: ScVal,
+}
+
+// Layout of one event in a `getEvents` response
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcEvent {
+    id: String,
+    ledger: u32,
+    #[serde(default)]
+    ledger_closed_at: String,
+    contract_id: String,
+    topic: Vec<String>,
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RpcDump {
+    // Full JSON-RPC response
+    Response { result: RpcEvents },
+    // `result` object only
+    Result(RpcEvents),
+    // Bare list of events
+    Events(Vec<RpcEvent>),
+}
+
+#[derive(Deserialize)]
+struct RpcEvents {
+    events: Vec<RpcEvent>,
+}
+
+/// Parse a JSON dump of `getEvents` results: the full response, its
+/// `result` object or the bare `events` array
+pub fn parse_dump(json: &str) -> Result<Vec<RawEvent>, Error> {
+    let dump: RpcDump =
+        serde_json::from_str(json).map_err(|err| Error::InvalidDump(err.to_string()))?;
+
+    let events = match dump {
+        RpcDump::Response { result } => result.events,
+        RpcDump::Result(result) => result.events,
+        RpcDump::Events(events) => events,
+    };
+
+    events
+        .into_iter()
+        .map(|event| {
+            Ok(RawEvent {
+                topics: event
+                    .topic
+                    .iter()
+                    .map(|topic| ScVal::from_xdr_base64(topic, Limits::none()))
+                    .collect::<Result<_, _>>()?,
+                value: ScVal::from_xdr_base64(&event.value, Limits::none())?,
+                id: event.id,
+                ledger: event.ledger,
+                ledger_closed_at: event.ledger_closed_at,
+                contract_id: event.contract_id,
+            })
+        })
+        .collect()
+}
+
+/// Local stand-in for RPC: the events published by the last invocation in
+/// a soroban test environment, numbered from `first_index` in `ledger`
+pub fn capture(env: &Env, ledger: u32, first_index: u32) -> Vec<RawEvent> {
+    env.events()
+        .all()
+        .iter()
+        .zip(first_index..)
+        .map(|((contract, topics, data), index)| RawEvent {
+            id: format!("{ledger:019}-{index:010}"),
+            ledger,
+            ledger_closed_at: String::new(),
+            contract_id: ScAddress::from(&contract).to_string(),
+            topics: topics
+                .iter()
+                .map(|topic| ScVal::try_from_val(env, &topic).unwrap())
+                .collect(),
+            value: ScVal::try_from_val(env, &data).unwrap(),
+        })
+        .collect()
+}
@@ -0,0 +1,169 @@
+use serde_json::json;
+use soroban_sdk::{
+    xdr::{Limits, ScAddress, WriteXdr},
+    Address, Env,
+};
+use turista_indexer::{capture, parse_dump, Contracts, Database, Decoder, RawEvent};
+use turista_test_support::{Turista, PLAZA_DE_ARMAS, QORICANCHA, TUR_REWARD_AMOUNT};
+
+// Collects the events of each invocation, as RPC would return them
+struct Recorder<'a> {
+    env: &'a Env,
+    ledger: u32,
+    events: Vec<RawEvent>,
+}
+
+impl<'a> Recorder<'a> {
+    fn new(env: &'a Env) -> Self {
+        Recorder {
+            env,
+            ledger: 0,
+            events: Vec::new(),
+        }
+    }
+
+    // Called after each invocation, which is closed in its own ledger
+    fn record(&mut self) {
+        self.ledger += 1;
+        self.events.extend(capture(self.env, self.ledger, 1));
+    }
+}
+
+fn strkey(address: &Address) -> String {
+    ScAddress::from(address).to_string()
+}
+
+fn decoder(turista: &Turista) -> Decoder {
+    Decoder::new(Contracts {
+        tur_token: strkey(&turista.tur.address),
+        checkin_nft: strkey(&turista.nft.address),
+        marketplace: strkey(&turista.marketplace.address),
+    })
+}
+
+#[test]
+fn test_indexes_collection_balances_and_sales() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let mut recorder = Recorder::new(&env);
+
+    let amount_xlm = 20_0000000i128; // 20 XLM
+    let buyer = turista.funded_buyer(amount_xlm);
+    let friend = turista.traveler();
+    let merchant = turista.merchant();
+
+    turista.check_in(&buyer, &PLAZA_DE_ARMAS);
+    recorder.record();
+    turista.check_in(&buyer, &QORICANCHA);
+    recorder.record();
+    turista.tur.transfer(&buyer, &friend, &TUR_REWARD_AMOUNT);
+    recorder.record();
+    turista.tur.approve(
+        &buyer,
+        &turista.marketplace.address,
+        &TUR_REWARD_AMOUNT,
+        &turista.approval_expiration(),
+    );
+    recorder.record();
+    turista
+        .marketplace
+        .purchase_with_discount(&buyer, &merchant, &amount_xlm, &TUR_REWARD_AMOUNT);
+    recorder.record();
+
+    let mut db = Database::open_in_memory().unwrap();
+    let decoder = decoder(&turista);
+    assert_eq!(db.ingest(&decoder, &recorder.events).unwrap(), 10);
+
+    // Ingesting the same events again is a no-op
+    assert_eq!(db.ingest(&decoder, &recorder.events).unwrap(), 0);
+    assert_eq!(db.latest_ledger().unwrap(), Some(5));
+
+    let collection = db.user_collection(&strkey(&buyer)).unwrap();
+    assert_eq!(collection.len(), 2);
+    assert_eq!(collection[0].place_id, PLAZA_DE_ARMAS.id);
+    assert_eq!(collection[1].place_name, QORICANCHA.name);
+
+    let rewards = db.user_rewards(&strkey(&buyer)).unwrap();
+    assert_eq!(rewards.len(), 2);
+    assert!(rewards.iter().all(|reward| reward.kind == "paid"));
+
+    let balances: Vec<i128> = db
+        .balance_history(&strkey(&buyer))
+        .unwrap()
+        .iter()
+        .map(|point| point.balance)
+        .collect();
+    assert_eq!(
+        balances,
+        [
+            TUR_REWARD_AMOUNT,
+            TUR_REWARD_AMOUNT * 2,
+            TUR_REWARD_AMOUNT,
+            0
+        ]
+    );
+
+    let sales = db.merchant_sales(&strkey(&merchant)).unwrap();
+    assert_eq!(sales.purchases.len(), 1);
+    assert_eq!(sales.purchases[0].buyer, strkey(&buyer));
+    assert_eq!(sales.total_xlm, amount_xlm);
+    assert_eq!(sales.platform_fee, amount_xlm / 100);
+    assert_eq!(sales.amount_tur_burned, TUR_REWARD_AMOUNT);
+    assert!(db
+        .merchant_sales(&strkey(&friend))
+        .unwrap()
+        .purchases
+        .is_empty());
+}
+
+#[test]
+fn test_ingests_rpc_event_dump() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let mut recorder = Recorder::new(&env);
+
+    let traveler = turista.traveler();
+    turista.check_in(&traveler, &PLAZA_DE_ARMAS);
+    recorder.record();
+
+    // Encode the events the way RPC `getEvents` returns them
+    let events: Vec<_> = recorder
+        .events
+        .iter()
+        .map(|event| {
+            json!({
+                "type": "contract",
+                "ledger": event.ledger,
+                "ledgerClosedAt": "2025-01-01T00:00:00Z",
+                "contractId": event.contract_id,
+                "id": event.id,
+                "topic": event
+                    .topics
+                    .iter()
+                    .map(|topic| topic.to_xdr_base64(Limits::none()).unwrap())
+                    .collect::<Vec<_>>(),
+                "value": event.value.to_xdr_base64(Limits::none()).unwrap(),
+            })
+        })
+        .collect();
+    let dump = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": { "events": events, "latestLedger": 1 },
+    });
+
+    let parsed = parse_dump(&dump.to_string()).unwrap();
+    assert_eq!(parsed.len(), recorder.events.len());
+    assert_eq!(parsed[0].topics, recorder.events[0].topics);
+
+    let mut db = Database::open_in_memory().unwrap();
+    db.ingest(&decoder(&turista), &parsed).unwrap();
+
+    let collection = db.user_collection(&strkey(&traveler)).unwrap();
+    assert_eq!(collection.len(), 1);
+    assert_eq!(collection[0].place_name, PLAZA_DE_ARMAS.name);
+    assert_eq!(
+        db.balance_history(&strkey(&traveler)).unwrap()[0].balance,
+        TUR_REWARD_AMOUNT
+    );
+}
@@ -0,0 +1,68 @@
+#![no_std]
+
+//! Cross-contract interfaces of the Turista contracts.
+//!
+//! Contracts call each other through the clients generated from these
+//! traits instead of `env.invoke_contract` with hand-written symbols, so a
+//! renamed function or a changed argument list fails to compile at the call
+//! site. Every trait mirrors the signature of the entrypoint it calls; the
+//! error type is `soroban_sdk::Error` so callers can use the `try_` variants
+//! without depending on the callee's crate. The standard token functions of
+//! `TurToken` are called through `soroban_sdk::token::TokenClient`.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Vec};
+
+// Proof that a user visited a place, returned by the check-in NFT contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VisitProof {
+    pub place_id: u32,
+    pub token_id: u64,
+    pub visited_at: u64, // Most recent visit to the place
+}
+
+/// Minting path of `TurToken` reserved to authorized minter contracts.
+/// `minter` must authorize the call, so a contract passes its own address.
+#[contractclient(name = "TurMinterClient")]
+pub trait TurMinterInterface {
+    fn mint_by_authorized(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), soroban_sdk::Error>;
+
+    fn remaining_minter_allowance(env: Env, minter: Address) -> i128;
+}
+
+/// Visit verification exposed by `CheckinNFTContract` to partner contracts
+#[contractclient(name = "CheckinVerifierClient")]
+pub trait CheckinVerifierInterface {
+    fn has_checked_in(env: Env, user: Address, place_id: u32) -> bool;
+
+    fn verify_visit(env: Env, user: Address, place_id: u32, since_timestamp: u64) -> Option<u64>;
+
+    fn verify_visits(
+        env: Env,
+        user: Address,
+        place_ids: Vec<u32>,
+        since_timestamp: u64,
+    ) -> Vec<VisitProof>;
+}
+
+// Traveler tier reached by staking TUR, lowest first
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Tier {
+    Standard = 0,
+    Silver = 1,
+    Gold = 2,
+}
+
+/// Tier lookup exposed by `Staking` to partner contracts, e.g. to scale
+/// marketplace discounts
+#[contractclient(name = "StakingTierClient")]
+pub trait StakingTierInterface {
+    fn tier_of(env: Env, user: Address) -> Tier;
+}
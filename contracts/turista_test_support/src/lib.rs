@@ -0,0 +1,168 @@
+//! Fixtures for tests spanning several Turista contracts.
+//!
+//! The real contracts are registered natively from their crates, so the
+//! tests run on a clean checkout without building any WASM first.
+
+use checkin_nft::{CheckinNFTContract, CheckinNFTContractClient};
+use marketplace::{Marketplace, MarketplaceClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, String,
+};
+use tur_token::{MinterLimits, TurToken, TurTokenClient};
+
+pub use checkin_nft::TUR_REWARD_AMOUNT;
+
+/// Initial TUR supply minted to the admin (10M TUR with 7 decimals)
+pub const TUR_INITIAL_SUPPLY: i128 = 10_000_000_0000000;
+
+/// Cap on the TUR supply (100M TUR with 7 decimals)
+pub const TUR_MAX_SUPPLY: i128 = 100_000_000_0000000;
+
+/// Check-in rewards the NFT contract may mint per day (10 check-ins)
+pub const CHECKIN_DAILY_MINT_LIMIT: i128 = 10 * TUR_REWARD_AMOUNT;
+
+/// A place travelers can check in at
+pub struct Place {
+    pub id: u32,
+    pub name: &'static str,
+    pub latitude: i64,  // Lat * 1e6
+    pub longitude: i64, // Lng * 1e6
+    pub image_url: &'static str,
+}
+
+pub const PLAZA_DE_ARMAS: Place = Place {
+    id: 1,
+    name: "Plaza de Armas",
+    latitude: -13516754,
+    longitude: -71978516,
+    image_url: "/nft-plaza-armas.png",
+};
+
+pub const QORICANCHA: Place = Place {
+    id: 2,
+    name: "Qoricancha",
+    latitude: -13519722,
+    longitude: -71975556,
+    image_url: "/nft-qoricancha.png",
+};
+
+pub const SACSAYHUAMAN: Place = Place {
+    id: 3,
+    name: "Sacsayhuaman",
+    latitude: -13509722,
+    longitude: -71981944,
+    image_url: "/nft-sacsayhuaman.png",
+};
+
+/// The three Turista contracts deployed and wired together:
+/// TUR token, check-in NFT contract with minter rights, and marketplace
+/// settling in an XLM asset contract.
+pub struct Turista<'a> {
+    pub env: &'a Env,
+    pub admin: Address,
+    pub platform: Address,
+    pub tur: TurTokenClient<'a>,
+    pub nft: CheckinNFTContractClient<'a>,
+    pub marketplace: MarketplaceClient<'a>,
+    pub xlm: TokenClient<'a>,
+    pub xlm_admin: StellarAssetClient<'a>,
+}
+
+impl<'a> Turista<'a> {
+    /// Deploy and initialize every contract. All authorizations are mocked.
+    pub fn setup(env: &'a Env) -> Self {
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let admin = Address::generate(env);
+        let platform = Address::generate(env);
+
+        // TUR token
+        let tur_id = env.register(TurToken, ());
+        let tur = TurTokenClient::new(env, &tur_id);
+        tur.initialize(
+            &admin,
+            &String::from_str(env, "Turistas Token"),
+            &String::from_str(env, "TUR"),
+            &7,
+            &TUR_INITIAL_SUPPLY,
+            &TUR_MAX_SUPPLY,
+        );
+
+        // Check-in NFT contract, allowed to mint TUR rewards
+        let nft_id = env.register(CheckinNFTContract, ());
+        let nft = CheckinNFTContractClient::new(env, &nft_id);
+        nft.initialize(&admin, &tur_id);
+        tur.set_authorized_minter(&admin, &nft_id, &true);
+        tur.set_minter_limits(
+            &admin,
+            &nft_id,
+            &MinterLimits {
+                quota: TUR_MAX_SUPPLY - TUR_INITIAL_SUPPLY,
+                rate_limit: CHECKIN_DAILY_MINT_LIMIT,
+                period_seconds: 86_400,
+            },
+        );
+
+        // XLM asset contract
+        let xlm_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let xlm = TokenClient::new(env, &xlm_id);
+        let xlm_admin = StellarAssetClient::new(env, &xlm_id);
+
+        // Marketplace
+        let marketplace_id = env.register(Marketplace, ());
+        let marketplace = MarketplaceClient::new(env, &marketplace_id);
+        marketplace.initialize(&admin, &platform, &tur_id, &xlm_id);
+
+        Turista {
+            env,
+            admin,
+            platform,
+            tur,
+            nft,
+            marketplace,
+            xlm,
+            xlm_admin,
+        }
+    }
+
+    /// A traveler with no funds
+    pub fn traveler(&self) -> Address {
+        Address::generate(self.env)
+    }
+
+    /// A traveler holding `amount_xlm` XLM
+    pub fn funded_buyer(&self, amount_xlm: i128) -> Address {
+        let buyer = self.traveler();
+        self.xlm_admin.mint(&buyer, &amount_xlm);
+        buyer
+    }
+
+    /// A merchant registered in the marketplace directory
+    pub fn merchant(&self) -> Address {
+        let merchant = Address::generate(self.env);
+        self.marketplace.set_merchant(&merchant, &true);
+        merchant
+    }
+
+    /// Expiration ledger for TUR allowances that outlive the test
+    pub fn approval_expiration(&self) -> u32 {
+        self.env.ledger().sequence() + 1_000
+    }
+
+    /// Check `user` in at `place`, returning the NFT token id
+    pub fn check_in(&self, user: &Address, place: &Place) -> u64 {
+        self.nft.mint(
+            user,
+            &place.id,
+            &String::from_str(self.env, place.name),
+            &place.latitude,
+            &place.longitude,
+            &String::from_str(self.env, place.image_url),
+            &None,
+        )
+    }
+}
@@ -0,0 +1,277 @@
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, InvokeError,
+};
+use tur_token::{MinterLimits, TransferPolicy};
+use turista_test_support::{
+    Turista, CHECKIN_DAILY_MINT_LIMIT, PLAZA_DE_ARMAS, QORICANCHA, SACSAYHUAMAN,
+    TUR_INITIAL_SUPPLY, TUR_MAX_SUPPLY, TUR_REWARD_AMOUNT,
+};
+
+#[test]
+fn test_checkin_rewards_pay_for_discounted_purchase() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+
+    let amount_xlm = 30_0000000i128; // 30 XLM
+    let buyer = turista.funded_buyer(amount_xlm);
+    let merchant = turista.merchant();
+
+    // Check in at three places to earn TUR
+    for place in [&PLAZA_DE_ARMAS, &QORICANCHA, &SACSAYHUAMAN] {
+        turista.check_in(&buyer, place);
+    }
+    assert_eq!(turista.nft.get_user_nfts(&buyer).len(), 3);
+    assert_eq!(turista.tur.balance(&buyer), TUR_REWARD_AMOUNT * 3);
+
+    // Spend part of the rewards on a discounted purchase
+    let amount_tur = TUR_REWARD_AMOUNT * 2;
+    turista.tur.approve(
+        &buyer,
+        &turista.marketplace.address,
+        &amount_tur,
+        &turista.approval_expiration(),
+    );
+    turista
+        .marketplace
+        .purchase_with_discount(&buyer, &merchant, &amount_xlm, &amount_tur);
+
+    let platform_fee = amount_xlm / 100;
+    assert_eq!(turista.xlm.balance(&buyer), 0);
+    assert_eq!(turista.xlm.balance(&turista.platform), platform_fee);
+    assert_eq!(turista.xlm.balance(&merchant), amount_xlm - platform_fee);
+
+    // The TUR spent is burned
+    assert_eq!(turista.tur.balance(&buyer), TUR_REWARD_AMOUNT);
+    assert_eq!(
+        turista.tur.total_supply(),
+        TUR_INITIAL_SUPPLY + TUR_REWARD_AMOUNT
+    );
+}
+
+#[test]
+fn test_pending_rewards_can_be_spent_after_claim() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+
+    let amount_xlm = 10_0000000i128; // 10 XLM
+    let buyer = turista.funded_buyer(amount_xlm);
+    let merchant = turista.merchant();
+
+    // Check-in while the NFT contract cannot mint keeps the reward pending
+    turista
+        .tur
+        .set_authorized_minter(&turista.admin, &turista.nft.address, &false);
+    turista.check_in(&buyer, &PLAZA_DE_ARMAS);
+    assert_eq!(turista.tur.balance(&buyer), 0);
+    assert_eq!(turista.nft.get_pending_rewards(&buyer), TUR_REWARD_AMOUNT);
+
+    // Restoring minter rights lets the traveler claim and spend it
+    turista
+        .tur
+        .set_authorized_minter(&turista.admin, &turista.nft.address, &true);
+    turista.nft.claim_pending_rewards(&buyer);
+
+    turista.tur.approve(
+        &buyer,
+        &turista.marketplace.address,
+        &TUR_REWARD_AMOUNT,
+        &turista.approval_expiration(),
+    );
+    turista
+        .marketplace
+        .purchase_with_discount(&buyer, &merchant, &amount_xlm, &TUR_REWARD_AMOUNT);
+
+    assert_eq!(turista.tur.balance(&buyer), 0);
+    assert_eq!(turista.tur.total_supply(), TUR_INITIAL_SUPPLY);
+}
+
+#[test]
+fn test_rewards_over_the_daily_mint_limit_stay_pending() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+
+    // Check-ins beyond the daily limit of the NFT contract are not paid
+    let paid_checkins = CHECKIN_DAILY_MINT_LIMIT / TUR_REWARD_AMOUNT;
+    for _ in 0..paid_checkins {
+        turista.check_in(&turista.traveler(), &PLAZA_DE_ARMAS);
+    }
+    let late = turista.traveler();
+    turista.check_in(&late, &PLAZA_DE_ARMAS);
+
+    assert_eq!(turista.tur.balance(&late), 0);
+    assert_eq!(turista.nft.get_pending_rewards(&late), TUR_REWARD_AMOUNT);
+    assert_eq!(
+        turista.tur.remaining_minter_allowance(&turista.nft.address),
+        0
+    );
+
+    // The reward can be claimed the next day
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    turista.nft.claim_pending_rewards(&late);
+    assert_eq!(turista.tur.balance(&late), TUR_REWARD_AMOUNT);
+}
+
+#[test]
+fn test_pending_rewards_over_the_rate_limit_are_paid_in_parts() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+    let traveler = turista.traveler();
+
+    // Rewards pile up while the NFT contract cannot mint
+    turista
+        .tur
+        .set_authorized_minter(&turista.admin, &turista.nft.address, &false);
+    for place in [&PLAZA_DE_ARMAS, &QORICANCHA, &SACSAYHUAMAN] {
+        turista.check_in(&traveler, place);
+    }
+    assert_eq!(
+        turista.nft.get_pending_rewards(&traveler),
+        TUR_REWARD_AMOUNT * 3
+    );
+
+    // Minting resumes with a rate limit below the pending amount
+    turista
+        .tur
+        .set_authorized_minter(&turista.admin, &turista.nft.address, &true);
+    turista.tur.set_minter_limits(
+        &turista.admin,
+        &turista.nft.address,
+        &MinterLimits {
+            quota: TUR_MAX_SUPPLY - TUR_INITIAL_SUPPLY,
+            rate_limit: TUR_REWARD_AMOUNT * 2,
+            period_seconds: 86_400,
+        },
+    );
+
+    // Each claim pays what the limit allows and keeps the rest pending
+    assert_eq!(
+        turista.nft.claim_pending_rewards(&traveler),
+        TUR_REWARD_AMOUNT * 2
+    );
+    assert_eq!(
+        turista.nft.get_pending_rewards(&traveler),
+        TUR_REWARD_AMOUNT
+    );
+    assert_eq!(
+        turista.nft.try_claim_pending_rewards(&traveler),
+        Err(Ok(checkin_nft::Error::RewardPayoutFailed))
+    );
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    assert_eq!(
+        turista.nft.claim_pending_rewards(&traveler),
+        TUR_REWARD_AMOUNT
+    );
+    assert_eq!(turista.nft.get_pending_rewards(&traveler), 0);
+    assert_eq!(turista.tur.balance(&traveler), TUR_REWARD_AMOUNT * 3);
+}
+
+#[test]
+fn test_outsider_cannot_mint_as_the_checkin_contract() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+
+    // Only the traveler's own authorization is mocked: the check-in
+    // contract authorizes the reward mint by invoking the token itself
+    env.mock_all_auths();
+    let traveler = turista.traveler();
+    turista.check_in(&traveler, &PLAZA_DE_ARMAS);
+    assert_eq!(turista.tur.balance(&traveler), TUR_REWARD_AMOUNT);
+
+    // An outsider passing the check-in contract as minter is rejected
+    env.set_auths(&[]);
+    let attacker = turista.traveler();
+    let result =
+        turista
+            .tur
+            .try_mint_by_authorized(&turista.nft.address, &attacker, &TUR_REWARD_AMOUNT);
+    assert_eq!(result, Err(Err(InvokeError::Abort)));
+    assert_eq!(turista.tur.balance(&attacker), 0);
+}
+
+#[test]
+fn test_frozen_traveler_cannot_spend_at_merchants() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+
+    let amount_xlm = 10_0000000i128; // 10 XLM
+    let buyer = turista.funded_buyer(amount_xlm);
+    let merchant = turista.merchant();
+    turista.check_in(&buyer, &PLAZA_DE_ARMAS);
+    turista.tur.approve(
+        &buyer,
+        &turista.marketplace.address,
+        &TUR_REWARD_AMOUNT,
+        &turista.approval_expiration(),
+    );
+
+    // The marketplace cannot burn the TUR of a frozen account
+    turista.tur.freeze(&turista.admin, &buyer);
+    let result = turista.marketplace.try_purchase_with_discount(
+        &buyer,
+        &merchant,
+        &amount_xlm,
+        &TUR_REWARD_AMOUNT,
+    );
+    assert_eq!(
+        result,
+        Err(Err(InvokeError::Contract(
+            tur_token::Error::AccountFrozen as u32
+        )))
+    );
+    assert_eq!(turista.xlm.balance(&merchant), 0);
+
+    // The points are clawed back to the treasury
+    turista
+        .tur
+        .clawback(&turista.admin, &buyer, &TUR_REWARD_AMOUNT);
+    assert_eq!(turista.tur.balance(&buyer), 0);
+    assert_eq!(
+        turista.tur.balance(&turista.admin),
+        TUR_INITIAL_SUPPLY + TUR_REWARD_AMOUNT
+    );
+}
+
+#[test]
+fn test_non_transferable_points_can_still_be_redeemed() {
+    let env = Env::default();
+    let turista = Turista::setup(&env);
+
+    let amount_xlm = 10_0000000i128; // 10 XLM
+    let buyer = turista.funded_buyer(amount_xlm);
+    let merchant = turista.merchant();
+    turista.check_in(&buyer, &PLAZA_DE_ARMAS);
+    turista
+        .tur
+        .set_transfer_policy(&turista.admin, &TransferPolicy::AllowlistOnly);
+    turista
+        .tur
+        .set_allowlisted(&turista.admin, &merchant, &true);
+
+    // Points cannot be passed on to other travelers, only to merchants
+    let friend = Address::generate(&env);
+    assert_eq!(
+        turista
+            .tur
+            .try_transfer(&buyer, &friend, &TUR_REWARD_AMOUNT),
+        Err(Ok(tur_token::Error::RecipientNotAllowlisted.into()))
+    );
+    turista
+        .tur
+        .transfer(&buyer, &merchant, &(TUR_REWARD_AMOUNT / 2));
+
+    // Burning them for a discount is not a transfer
+    let amount_tur = TUR_REWARD_AMOUNT / 2;
+    turista.tur.approve(
+        &buyer,
+        &turista.marketplace.address,
+        &amount_tur,
+        &turista.approval_expiration(),
+    );
+    turista
+        .marketplace
+        .purchase_with_discount(&buyer, &merchant, &amount_xlm, &amount_tur);
+    assert_eq!(turista.tur.balance(&buyer), 0);
+    assert_eq!(turista.tur.balance(&merchant), TUR_REWARD_AMOUNT / 2);
+}
@@ -65,6 +65,8 @@ class CheckinService {
       const latScVal = nativeToScVal(Math.floor(params.latitude * 1e6), { type: 'i64' })
       const lngScVal = nativeToScVal(Math.floor(params.longitude * 1e6), { type: 'i64' })
       const imageScVal = this.stringToScVal(params.imageUrl)
+      // Photo proof (Option<PhotoProof>) is attached later with attach_photo
+      const photoScVal = xdr.ScVal.scvVoid()
 
       // 4. Build transaction (function is 'mint', not 'check_in')
       const transaction = new TransactionBuilder(account, {
@@ -79,7 +81,8 @@ class CheckinService {
             placeNameScVal,
             latScVal,
             lngScVal,
-            imageScVal
+            imageScVal,
+            photoScVal
           )
         )
         .setTimeout(30)
@@ -14,6 +14,8 @@ const MARKETPLACE_CONTRACT_ID = import.meta.env.VITE_MARKETPLACE_CONTRACT || ''
 const TUR_CONTRACT_ID = import.meta.env.VITE_TUR_TOKEN_CONTRACT || ''
 const SOROBAN_RPC_URL = import.meta.env.VITE_SOROBAN_RPC_URL || 'https://soroban-testnet.stellar.org'
 const NETWORK_PASSPHRASE = Networks.TESTNET
+// TUR allowances expire after about one day (5 second ledgers)
+const ALLOWANCE_LEDGERS = 17280
 
 class MarketplaceService {
   private rpcServer: SorobanRpc.Server
@@ -221,6 +223,7 @@ class MarketplaceService {
       
       const account = await this.rpcServer.getAccount(ownerAddress)
       const turContract = new Contract(TUR_CONTRACT_ID)
+      const { sequence } = await this.rpcServer.getLatestLedger()
       
       // Build transaction calling approve
       const transaction = new TransactionBuilder(account, {
@@ -232,7 +235,8 @@ class MarketplaceService {
             'approve',
             Address.fromString(ownerAddress).toScVal(),
             Address.fromString(MARKETPLACE_CONTRACT_ID).toScVal(),
-            nativeToScVal(Math.floor(amount * 1e7), { type: 'i128' })
+            nativeToScVal(Math.floor(amount * 1e7), { type: 'i128' }),
+            nativeToScVal(sequence + ALLOWANCE_LEDGERS, { type: 'u32' })
           )
         )
         .setTimeout(30)